@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError};
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = pending_admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> AcceptAdmin<'info> {
+    pub fn accept_admin(&mut self) -> Result<()> {
+        self.config.admin = self.pending_admin.key();
+        self.config.pending_admin = Pubkey::default();
+        Ok(())
+    }
+}
@@ -0,0 +1,370 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    accepts_public_credits, Bid, BidEvent, Config, Escrow, EscrowError, FeeEpoch, FeeOverride,
+    FeeStake, Fill, FillRecords, Leaderboard, MakerDirectory, PairStats, Points, PointsRate,
+    TakeEvent,
+};
+
+/// Sells a sealed-bid escrow's deposit to the maker's chosen bid. Every other bid can be
+/// withdrawn once the escrow has closed.
+#[derive(Accounts)]
+pub struct AcceptBid<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program,
+        constraint = !bidder_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&bidder_ata_a.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub bidder_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = escrow.sealed_bids @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = escrow,
+        has_one = bidder,
+        seeds = [b"bid", escrow.key().as_ref(), bidder.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Box<Account<'info, Bid>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program,
+    )]
+    pub bid_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"fee_override", escrow.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+    /// The bidder's staked protocol tokens, which discount the take fee by `config.stake_tiers`.
+    #[account(
+        seeds = [b"fee_stake", bidder.key().as_ref()],
+        bump = fee_stake.bump
+    )]
+    pub fee_stake: Option<Box<Account<'info, FeeStake>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = config.treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Current epoch's bucket for mint_b fees paid to the treasury, required when there is a fee.
+    #[account(
+        mut,
+        seeds = [b"fee_epoch", mint_b.key().as_ref(), fee_epoch.epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch.bump
+    )]
+    pub fee_epoch: Option<Box<Account<'info, FeeEpoch>>>,
+    /// Ranks the maker by mint_b volume filled.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", mint_b.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = pair_stats.bump
+    )]
+    pub pair_stats: Option<Box<Account<'info, PairStats>>>,
+    /// The maker's points, credited for the mint_a filled at `maker_points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", maker.key().as_ref()],
+        bump = maker_points.bump
+    )]
+    pub maker_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_a.key().as_ref()],
+        bump = maker_points_rate.bump
+    )]
+    pub maker_points_rate: Option<Box<Account<'info, PointsRate>>>,
+    /// The bidder's points, credited for the mint_b paid at `points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", bidder.key().as_ref()],
+        bump = bidder_points.bump
+    )]
+    pub bidder_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_b.key().as_ref()],
+        bump = points_rate.bump
+    )]
+    pub points_rate: Option<Box<Account<'info, PointsRate>>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the bidder's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", bidder.key().as_ref()],
+        bump,
+        constraint = bidder_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub bidder_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptBid<'info> {
+    pub fn check_active(&self) -> Result<()> {
+        self.escrow.check_open(
+            &self.bidder.key(),
+            Clock::get()?.unix_timestamp,
+            &self.config,
+        )
+    }
+
+    pub fn take_fee(&self) -> Result<u64> {
+        self.config.take_fee(
+            self.bid.amount,
+            self.fee_override.as_deref(),
+            self.fee_stake.as_deref().map(|fee_stake| &**fee_stake),
+        )
+    }
+
+    /// Pays the bid out of its vault, less the take fee, and closes the vault to the bidder.
+    pub fn deposit_and_close_bid_vault(&mut self) -> Result<()> {
+        let escrow = self.escrow.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"bid",
+            escrow.as_ref(),
+            self.bidder.to_account_info().key.as_ref(),
+            &[self.bid.bump],
+        ]];
+
+        let fee = self.take_fee()?;
+
+        if fee > 0 {
+            let treasury_ata_b = self
+                .treasury_ata_b
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.bid_vault.to_account_info(),
+                mint: self.mint_b.to_account_info(),
+                to: treasury_ata_b.to_account_info(),
+                authority: self.bid.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                transfer_accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(cpi_ctx, fee, self.mint_b.decimals)?;
+        }
+
+        let transfer_accounts = TransferChecked {
+            from: self.bid_vault.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.bid.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(
+            cpi_ctx,
+            self.bid
+                .amount
+                .checked_sub(fee)
+                .ok_or(EscrowError::Overflow)?,
+            self.mint_b.decimals,
+        )?;
+
+        let accounts = CloseAccount {
+            account: self.bid_vault.to_account_info(),
+            destination: self.bidder.to_account_info(),
+            authority: self.bid.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+
+    pub fn record_fill(&mut self) -> Result<()> {
+        let fill = Fill {
+            maker: self.maker.key(),
+            taker: self.bidder.key(),
+            amount: self.vault.amount,
+            receive: self.bid.amount,
+            treasury_fee: self.take_fee()?,
+            rebate: 0,
+        };
+
+        FillRecords {
+            pair_stats: self
+                .pair_stats
+                .as_deref_mut()
+                .map(|pair_stats| &mut **pair_stats),
+            fee_epoch: self
+                .fee_epoch
+                .as_deref_mut()
+                .map(|fee_epoch| &mut **fee_epoch),
+            maker_points: self
+                .maker_points
+                .as_deref_mut()
+                .map(|maker_points| &mut **maker_points),
+            maker_points_rate: self
+                .maker_points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            taker_points: self
+                .bidder_points
+                .as_deref_mut()
+                .map(|bidder_points| &mut **bidder_points),
+            points_rate: self
+                .points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            leaderboard: self
+                .leaderboard
+                .as_deref_mut()
+                .map(|leaderboard| &mut **leaderboard),
+            ..Default::default()
+        }
+        .record(&fill)
+    }
+
+    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
+        let seed = self.escrow.seed;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.bidder_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        emit!(BidEvent {
+            escrow: self.escrow.key(),
+            bid: self.bid.key(),
+            bidder: self.bidder.key(),
+            amount: self.bid.amount,
+            accepted: true,
+        });
+
+        emit!(TakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            taker: self.bidder.key(),
+            amount: self.vault.amount,
+            receive: self.bid.amount,
+            fee: self.take_fee()?,
+            protocol_fee: 0,
+            referrer: None,
+            referral_fee: 0,
+            donation: 0,
+            frontend_fee: 0,
+            note: self.escrow.note,
+        });
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&seed);
+        }
+        Ok(())
+    }
+}
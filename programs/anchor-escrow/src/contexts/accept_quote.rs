@@ -0,0 +1,163 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{EscrowError, Quote, QuoteEvent, Rfq};
+
+#[derive(Accounts)]
+pub struct AcceptQuote<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    #[account(mut)]
+    pub market_maker: SystemAccount<'info>,
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = requester,
+        associated_token::mint = mint_a,
+        associated_token::authority = requester,
+        associated_token::token_program = token_program,
+    )]
+    pub requester_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = requester,
+        associated_token::token_program = token_program,
+    )]
+    pub requester_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = requester,
+        associated_token::mint = mint_b,
+        associated_token::authority = market_maker,
+        associated_token::token_program = token_program,
+    )]
+    pub market_maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = requester,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !rfq.closed @ EscrowError::RfqClosed,
+        seeds = [b"rfq", requester.key().as_ref(), rfq.seed.as_ref()],
+        bump = rfq.bump
+    )]
+    pub rfq: Box<Account<'info, Rfq>>,
+    #[account(
+        mut,
+        close = market_maker,
+        has_one = rfq,
+        has_one = market_maker,
+        seeds = [b"quote", rfq.key().as_ref(), market_maker.key().as_ref()],
+        bump = quote.bump
+    )]
+    pub quote: Box<Account<'info, Quote>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = quote,
+        associated_token::token_program = token_program,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: the requester's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", requester.key().as_ref()],
+        bump,
+        constraint = requester_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub requester_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the market maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", market_maker.key().as_ref()],
+        bump,
+        constraint = market_maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub market_maker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AcceptQuote<'info> {
+    pub fn deposit(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.rfq.expires_at,
+            EscrowError::RfqExpired
+        );
+
+        let transfer_accounts = TransferChecked {
+            from: self.requester_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.market_maker_ata_b.to_account_info(),
+            authority: self.requester.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, self.quote.receive, self.mint_b.decimals)
+    }
+
+    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
+        let rfq = self.rfq.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"quote",
+            rfq.as_ref(),
+            self.market_maker.to_account_info().key.as_ref(),
+            &[self.quote.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.requester_ata_a.to_account_info(),
+            authority: self.quote.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        emit!(QuoteEvent {
+            rfq,
+            quote: self.quote.key(),
+            market_maker: self.market_maker.key(),
+            receive: self.quote.receive,
+            accepted: true,
+        });
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.market_maker.to_account_info(),
+            authority: self.quote.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+
+    /// The RFQ is filled; it closes once the other quotes have been withdrawn.
+    pub fn close_rfq(&mut self) -> Result<()> {
+        self.rfq.closed = true;
+        if self.rfq.release_quote() {
+            self.rfq.close(self.requester.to_account_info())?;
+        }
+        Ok(())
+    }
+}
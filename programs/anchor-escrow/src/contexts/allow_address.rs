@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, DenylistEntry, EscrowError};
+
+#[derive(Accounts)]
+pub struct AllowAddress<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"denylist", denylist_entry.address.as_ref()],
+        bump = denylist_entry.bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{Config, EscrowError, InsuranceClaim, InsurancePool};
+
+/// Pays an insurance claim from the pool and closes it, returning its rent to the claimant.
+#[derive(Accounts)]
+pub struct ApproveInsuranceClaim<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub claimant: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        has_one = mint,
+        seeds = [b"insurance_pool", mint.key().as_ref()],
+        bump = insurance_pool.bump
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = insurance_pool,
+        associated_token::token_program = token_program
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = claimant,
+        has_one = claimant,
+        constraint = insurance_claim.pool == insurance_pool.key(),
+        seeds = [b"insurance_claim", insurance_claim.escrow.as_ref(), claimant.key().as_ref()],
+        bump = insurance_claim.bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        associated_token::mint = mint,
+        associated_token::authority = claimant,
+        associated_token::token_program = token_program
+    )]
+    pub claimant_ata: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ApproveInsuranceClaim<'info> {
+    pub fn approve_insurance_claim(&mut self) -> Result<()> {
+        let amount = self.insurance_claim.amount;
+        require_gte!(
+            self.insurance_vault.amount,
+            amount,
+            EscrowError::InvalidClaimAmount
+        );
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"insurance_pool",
+            self.insurance_pool.mint.as_ref(),
+            &[self.insurance_pool.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.insurance_vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.claimant_ata.to_account_info(),
+            authority: self.insurance_pool.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, amount, self.mint.decimals)?;
+
+        self.insurance_pool.claims_paid = self
+            .insurance_pool
+            .claims_paid
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,152 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{Config, Escrow, EscrowError, Keeper, MakerDirectory, RefundEvent};
+
+/// Permissionless refund of an expired escrow, meant to be scheduled by keepers. The deposit can
+/// only go to the maker's mint_a ATA; the executor earns `config.auto_refund_tip` from the rent,
+/// credited to their `Keeper` account when one is supplied.
+#[derive(Accounts)]
+pub struct AutoRefund<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = executor,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = mint_a,
+        has_one = maker,
+        constraint = !escrow.delegated @ EscrowError::InvalidEscrowMode,
+        constraint = !escrow.is_streaming() @ EscrowError::AlreadyTaken,
+        constraint = escrow.is_expired(Clock::get()?.unix_timestamp, config.max_duration) @ EscrowError::NotExpired,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [b"keeper", executor.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> AutoRefund<'info> {
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+        }
+        Ok(())
+    }
+
+    pub fn pay_tip(&mut self) -> Result<()> {
+        let escrow = self.escrow.to_account_info();
+        let tip = self.config.auto_refund_tip.min(escrow.lamports());
+
+        if let Some(keeper) = self.keeper.as_mut() {
+            keeper.record_crank(tip)?;
+            if tip > 0 {
+                escrow.sub_lamports(tip)?;
+                keeper.add_lamports(tip)?;
+            }
+            return Ok(());
+        }
+
+        if tip > 0 {
+            escrow.sub_lamports(tip)?;
+            self.executor.add_lamports(tip)?;
+        }
+        Ok(())
+    }
+
+    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        let amount = self.vault.amount;
+
+        if amount > 0 {
+            let xfer_accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.maker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                xfer_accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(ctx, amount, self.mint_a.decimals)?;
+        }
+
+        emit!(RefundEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            amount,
+            fee: 0,
+        });
+
+        let close_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{burn, Burn, Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{invoke_route, BuybackConfig, BuybackEvent, Config, EscrowError, SwapRouter};
+
+/// Swaps up to `amount_in` of the treasury's accumulated `fee_mint` fees into the buyback token
+/// through an admin-registered router and burns everything the swap bought. The treasury signs,
+/// since its token accounts are not program-owned.
+#[derive(Accounts)]
+pub struct BuybackAndBurn<'info> {
+    #[account(mut, address = config.treasury @ EscrowError::Unauthorized)]
+    pub treasury: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"buyback_config"],
+        bump = buyback_config.bump
+    )]
+    pub buyback_config: Box<Account<'info, BuybackConfig>>,
+    #[account(
+        constraint = fee_mint.key() != burn_mint.key() @ EscrowError::InvalidBuybackMint
+    )]
+    pub fee_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        address = buyback_config.mint @ EscrowError::InvalidBuybackMint
+    )]
+    pub burn_mint: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = fee_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_fee_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Receives the swap output, which is then burned.
+    #[account(
+        init_if_needed,
+        payer = treasury,
+        associated_token::mint = burn_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_burn_ata: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: swap program, which must be registered in `swap_router`.
+    #[account(executable, address = swap_router.program @ EscrowError::RouterNotAllowed)]
+    pub router: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"swap_router", swap_router.program.as_ref()],
+        bump = swap_router.bump
+    )]
+    pub swap_router: Box<Account<'info, SwapRouter>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> BuybackAndBurn<'info> {
+    /// The route must write its output to `treasury_burn_ata`, spend at most `amount_in` fee
+    /// tokens and buy at least `min_out` burn tokens.
+    pub fn swap(
+        &mut self,
+        amount_in: u64,
+        min_out: u64,
+        route_data: Vec<u8>,
+        route_accounts: &[AccountInfo<'info>],
+    ) -> Result<u64> {
+        require_gte!(
+            self.buyback_config.max_amount_in,
+            amount_in,
+            EscrowError::BuybackLimitExceeded
+        );
+        require!(
+            route_accounts
+                .iter()
+                .any(|account| *account.key == self.treasury_burn_ata.key() && account.is_writable),
+            EscrowError::InvalidRouteOutput
+        );
+
+        let fee_before = self.treasury_fee_ata.amount;
+        let burn_before = self.treasury_burn_ata.amount;
+
+        invoke_route(&self.router.to_account_info(), route_data, route_accounts)?;
+
+        self.treasury_fee_ata.reload()?;
+        self.treasury_burn_ata.reload()?;
+        require_keys_eq!(
+            self.treasury_burn_ata.owner,
+            self.treasury.key(),
+            EscrowError::InvalidRouteOutput
+        );
+        require_gte!(
+            amount_in,
+            fee_before.saturating_sub(self.treasury_fee_ata.amount),
+            EscrowError::BuybackLimitExceeded
+        );
+
+        let bought = self.treasury_burn_ata.amount.saturating_sub(burn_before);
+        require_gte!(bought, min_out, EscrowError::InvalidRouteOutput);
+        Ok(bought)
+    }
+
+    pub fn burn(&mut self, amount: u64) -> Result<()> {
+        let accounts = Burn {
+            mint: self.burn_mint.to_account_info(),
+            from: self.treasury_burn_ata.to_account_info(),
+            authority: self.treasury.to_account_info(),
+        };
+
+        let ctx = CpiContext::new(self.token_program.to_account_info(), accounts);
+
+        burn(ctx, amount)?;
+
+        emit!(BuybackEvent {
+            fee_mint: self.fee_mint.key(),
+            burn_mint: self.burn_mint.key(),
+            burned: amount,
+        });
+        Ok(())
+    }
+}
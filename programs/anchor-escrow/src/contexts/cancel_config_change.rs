@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError, PendingConfigChange};
+
+#[derive(Accounts)]
+pub struct CancelConfigChange<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"pending_config_change"],
+        bump = pending_config_change.bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+}
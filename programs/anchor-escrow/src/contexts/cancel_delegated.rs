@@ -0,0 +1,146 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use anchor_spl::token_interface::{
+    revoke, transfer_checked, Mint, Revoke, TokenAccount, TokenInterface, TransferChecked,
+};
+
+use crate::{bps_of, Config, Escrow, EscrowError, MakerDirectory, RefundEvent};
+
+#[derive(Accounts)]
+pub struct CancelDelegated<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = mint_a,
+        has_one = maker,
+        constraint = escrow.delegated @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    pub treasury_ata_a: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelDelegated<'info> {
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+        }
+        Ok(())
+    }
+
+    pub fn check_unlocked(&self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.escrow.locked_until,
+            EscrowError::RefundLocked
+        );
+        Ok(())
+    }
+
+    pub fn pay_cancellation_fee(&mut self) -> Result<()> {
+        // Cancellation fees only apply to offers withdrawn before they expire.
+        let expired = self
+            .escrow
+            .is_expired(Clock::get()?.unix_timestamp, self.config.max_duration);
+
+        if !expired && self.config.cancellation_fee > 0 {
+            let transfer_accounts = Transfer {
+                from: self.maker.to_account_info(),
+                to: self.treasury.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+            transfer(cpi_ctx, self.config.cancellation_fee)?;
+        }
+
+        let fee = if expired {
+            0
+        } else {
+            bps_of(self.escrow.amount, self.config.cancellation_fee_bps)?
+        };
+
+        if fee > 0 {
+            let treasury_ata_a = self
+                .treasury_ata_a
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.maker_ata_a.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: treasury_ata_a.to_account_info(),
+                authority: self.maker.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+            transfer_checked(cpi_ctx, fee, self.mint_a.decimals)?;
+        }
+
+        emit!(RefundEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            amount: self
+                .escrow
+                .amount
+                .checked_sub(fee)
+                .ok_or(EscrowError::Overflow)?,
+            fee,
+        });
+        Ok(())
+    }
+
+    pub fn revoke(&mut self) -> Result<()> {
+        // Leave approvals granted to anyone else untouched.
+        if self.maker_ata_a.delegate != Some(self.escrow.key()).into() {
+            return Ok(());
+        }
+
+        let revoke_accounts = Revoke {
+            source: self.maker_ata_a.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), revoke_accounts);
+
+        revoke(cpi_ctx)
+    }
+}
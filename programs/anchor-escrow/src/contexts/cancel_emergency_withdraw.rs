@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EmergencyWithdrawal, EscrowError};
+
+#[derive(Accounts)]
+pub struct CancelEmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency_withdrawal", emergency_withdrawal.escrow.as_ref()],
+        bump = emergency_withdrawal.bump
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::Rfq;
+
+/// Stops the RFQ taking quotes. It closes now if none are open, or else once the last is withdrawn.
+#[derive(Accounts)]
+pub struct CancelRfq<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    #[account(
+        mut,
+        has_one = requester,
+        seeds = [b"rfq", requester.key().as_ref(), rfq.seed.as_ref()],
+        bump = rfq.bump
+    )]
+    pub rfq: Account<'info, Rfq>,
+}
+
+impl<'info> CancelRfq<'info> {
+    pub fn cancel_rfq(&mut self) -> Result<()> {
+        if self.rfq.open_quotes == 0 {
+            return self.rfq.close(self.requester.to_account_info());
+        }
+        self.rfq.closed = true;
+        Ok(())
+    }
+}
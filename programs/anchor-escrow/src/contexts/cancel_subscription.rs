@@ -0,0 +1,129 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{EscrowError, Subscription};
+
+/// Ends a subscription early. Tranches already due still go to the taker; the rest is refunded.
+#[derive(Accounts)]
+pub struct CancelSubscription<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub taker: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = taker,
+        has_one = mint,
+        seeds = [b"subscription", maker.key().as_ref(), subscription.seed.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = subscription,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CancelSubscription<'info> {
+    fn transfer_from_vault(
+        &self,
+        to: &InterfaceAccount<'info, TokenAccount>,
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"subscription",
+            self.maker.to_account_info().key.as_ref(),
+            &self.subscription.seed[..],
+            &[self.subscription.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: to.to_account_info(),
+            authority: self.subscription.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, amount, self.mint.decimals)
+    }
+
+    pub fn settle_and_close_vault(&mut self) -> Result<()> {
+        let due = self
+            .subscription
+            .due(Clock::get()?.unix_timestamp)
+            .checked_sub(self.subscription.claimed)
+            .ok_or(EscrowError::Overflow)?;
+        let owed = (due as u64).saturating_mul(self.subscription.amount_per_period);
+        let owed = owed.min(self.vault.amount);
+
+        self.transfer_from_vault(&self.taker_ata, owed)?;
+        self.transfer_from_vault(
+            &self.maker_ata,
+            self.vault
+                .amount
+                .checked_sub(owed)
+                .ok_or(EscrowError::Overflow)?,
+        )?;
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"subscription",
+            self.maker.to_account_info().key.as_ref(),
+            &self.subscription.seed[..],
+            &[self.subscription.bump],
+        ]];
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.subscription.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+}
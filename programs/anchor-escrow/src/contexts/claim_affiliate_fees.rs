@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::Affiliate;
+
+#[derive(Accounts)]
+pub struct ClaimAffiliateFees<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        has_one = owner,
+        has_one = mint,
+        seeds = [b"affiliate", owner.key().as_ref(), mint.key().as_ref()],
+        bump = affiliate.bump
+    )]
+    pub affiliate: Account<'info, Affiliate>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = affiliate,
+        associated_token::token_program = token_program
+    )]
+    pub affiliate_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_ata: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimAffiliateFees<'info> {
+    pub fn claim_affiliate_fees(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"affiliate",
+            self.affiliate.owner.as_ref(),
+            self.affiliate.mint.as_ref(),
+            &[self.affiliate.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.affiliate_vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.owner_ata.to_account_info(),
+            authority: self.affiliate.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.affiliate.accrued, self.mint.decimals)?;
+
+        self.affiliate.accrued = 0;
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::Keeper;
+
+#[derive(Accounts)]
+pub struct ClaimKeeperRewards<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        has_one = authority,
+        seeds = [b"keeper", authority.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Account<'info, Keeper>,
+}
+
+impl<'info> ClaimKeeperRewards<'info> {
+    pub fn claim_keeper_rewards(&mut self) -> Result<()> {
+        let rewards = self.keeper.rewards;
+
+        if rewards > 0 {
+            self.keeper.sub_lamports(rewards)?;
+            self.authority.add_lamports(rewards)?;
+        }
+
+        self.keeper.rewards = 0;
+        Ok(())
+    }
+}
@@ -0,0 +1,115 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{EscrowError, Subscription};
+
+/// Pays the taker the next due tranche. Claiming the last tranche closes the subscription.
+#[derive(Accounts)]
+pub struct ClaimPeriod<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = taker,
+        has_one = mint,
+        seeds = [b"subscription", maker.key().as_ref(), subscription.seed.as_ref()],
+        bump = subscription.bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = subscription,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimPeriod<'info> {
+    pub fn claim_period(&mut self) -> Result<()> {
+        require!(
+            self.subscription.claimed < self.subscription.due(Clock::get()?.unix_timestamp),
+            EscrowError::NothingToClaim
+        );
+
+        let seed = self.subscription.seed;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"subscription",
+            self.maker.to_account_info().key.as_ref(),
+            &seed[..],
+            &[self.subscription.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.taker_ata.to_account_info(),
+            authority: self.subscription.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.subscription.amount_per_period, self.mint.decimals)?;
+
+        self.subscription.claimed += 1;
+        if self.subscription.claimed < self.subscription.periods {
+            return Ok(());
+        }
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.subscription.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        self.subscription.close(self.maker.to_account_info())
+    }
+}
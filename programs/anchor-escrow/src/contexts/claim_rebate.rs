@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::TakerRebate;
+
+#[derive(Accounts)]
+pub struct ClaimRebate<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        has_one = taker,
+        has_one = mint,
+        seeds = [b"taker_rebate", taker.key().as_ref(), mint.key().as_ref()],
+        bump = taker_rebate.bump
+    )]
+    pub taker_rebate: Account<'info, TakerRebate>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = taker_rebate,
+        associated_token::token_program = token_program
+    )]
+    pub rebate_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimRebate<'info> {
+    pub fn claim_rebate(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"taker_rebate",
+            self.taker_rebate.taker.as_ref(),
+            self.taker_rebate.mint.as_ref(),
+            &[self.taker_rebate.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.rebate_vault.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.taker_ata.to_account_info(),
+            authority: self.taker_rebate.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.taker_rebate.accrued, self.mint.decimals)?;
+
+        self.taker_rebate.accrued = 0;
+        Ok(())
+    }
+}
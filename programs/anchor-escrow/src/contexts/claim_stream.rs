@@ -0,0 +1,113 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{Escrow, EscrowError};
+
+/// Releases the deposit of a taken streaming escrow to its taker as it vests. The claim that
+/// completes the stream closes the vault and escrow.
+#[derive(Accounts)]
+pub struct ClaimStream<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program
+    )]
+    pub taker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = escrow.stream_taker == taker.key() @ EscrowError::Unauthorized,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimStream<'info> {
+    pub fn claim_stream(&mut self) -> Result<()> {
+        let vested = self.escrow.vested(Clock::get()?.unix_timestamp)?;
+        let complete = vested == self.escrow.amount;
+
+        // The final claim also sweeps anything sent to the vault on top of the deposit.
+        let amount = if complete {
+            self.vault.amount
+        } else {
+            vested
+                .checked_sub(self.escrow.streamed)
+                .ok_or(EscrowError::Overflow)?
+        };
+        require!(amount > 0 || complete, EscrowError::NothingToClaim);
+
+        let seed = self.escrow.seed;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        if amount > 0 {
+            let accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.taker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(ctx, amount, self.mint_a.decimals)?;
+        }
+
+        self.escrow.streamed = vested;
+        if !complete {
+            return Ok(());
+        }
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        self.escrow.close(self.maker.to_account_info())
+    }
+}
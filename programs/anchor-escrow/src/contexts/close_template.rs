@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+use crate::Template;
+
+#[derive(Accounts)]
+pub struct CloseTemplate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        close = owner,
+        has_one = owner,
+        seeds = [b"template", owner.key().as_ref(), template.id.to_le_bytes().as_ref()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, Template>,
+}
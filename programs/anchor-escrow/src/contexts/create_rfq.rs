@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{EscrowError, Rfq};
+
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct CreateRfq<'info> {
+    #[account(mut)]
+    pub requester: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = requester,
+        space = 8 + Rfq::INIT_SPACE,
+        seeds = [b"rfq", requester.key().as_ref(), seed.as_ref()],
+        bump
+    )]
+    pub rfq: Account<'info, Rfq>,
+    /// CHECK: the requester's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", requester.key().as_ref()],
+        bump,
+        constraint = requester_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub requester_denylist_entry: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRfq<'info> {
+    pub fn create_rfq(
+        &mut self,
+        seed: [u8; 32],
+        amount: u64,
+        bond: u64,
+        expires_at: i64,
+        bumps: &CreateRfqBumps,
+    ) -> Result<()> {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            EscrowError::InvalidDuration
+        );
+
+        self.rfq.set_inner(Rfq {
+            seed,
+            requester: self.requester.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount,
+            bond,
+            expires_at,
+            open_quotes: 0,
+            closed: false,
+            bump: bumps.rfq,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{approve, Approve, TokenAccount, TokenInterface};
+
+use crate::{EscrowError, SessionToken};
+
+/// Registers `signer` as a session key for the authority and approves the session token to spend
+/// up to `allowance` from `token_account` on its takes.
+#[derive(Accounts)]
+#[instruction(signer: Pubkey)]
+pub struct CreateSession<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::token_program = token_program
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + SessionToken::INIT_SPACE,
+        seeds = [b"session_token", authority.key().as_ref(), signer.as_ref()],
+        bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateSession<'info> {
+    pub fn create_session(
+        &mut self,
+        signer: Pubkey,
+        expires_at: i64,
+        allowance: u64,
+        bumps: &CreateSessionBumps,
+    ) -> Result<()> {
+        require!(
+            expires_at > Clock::get()?.unix_timestamp,
+            EscrowError::InvalidDuration
+        );
+
+        self.session_token.set_inner(SessionToken {
+            authority: self.authority.key(),
+            signer,
+            expires_at,
+            bump: bumps.session_token,
+        });
+
+        let accounts = Approve {
+            to: self.token_account.to_account_info(),
+            delegate: self.session_token.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        approve(
+            CpiContext::new(self.token_program.to_account_info(), accounts),
+            allowance,
+        )
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenInterface};
+
+use crate::{Template, TemplateParams};
+
+#[derive(Accounts)]
+#[instruction(id: u32)]
+pub struct CreateTemplate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Template::INIT_SPACE,
+        seeds = [b"template", owner.key().as_ref(), id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub template: Account<'info, Template>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTemplate<'info> {
+    pub fn create_template(
+        &mut self,
+        id: u32,
+        params: TemplateParams,
+        bumps: &CreateTemplateBumps,
+    ) -> Result<()> {
+        params.validate()?;
+
+        self.template.set_inner(Template {
+            owner: self.owner.key(),
+            id,
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            price_numerator: params.price_numerator,
+            price_denominator: params.price_denominator,
+            duration: params.duration,
+            allow_partial_fills: params.allow_partial_fills,
+            unwrap_sol: params.unwrap_sol,
+            bump: bumps.template,
+        });
+        Ok(())
+    }
+}
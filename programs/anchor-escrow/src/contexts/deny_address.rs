@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, DenylistEntry, EscrowError};
+
+#[derive(Accounts)]
+#[instruction(address: Pubkey)]
+pub struct DenyAddress<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + DenylistEntry::INIT_SPACE,
+        seeds = [b"denylist", address.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> DenyAddress<'info> {
+    pub fn deny_address(&mut self, address: Pubkey, bumps: &DenyAddressBumps) -> Result<()> {
+        self.denylist_entry.set_inner(DenylistEntry {
+            address,
+            bump: bumps.denylist_entry,
+        });
+        Ok(())
+    }
+}
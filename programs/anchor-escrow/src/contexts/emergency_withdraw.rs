@@ -0,0 +1,133 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    Config, EmergencyWithdrawEvent, EmergencyWithdrawal, Escrow, EscrowError, MakerDirectory,
+};
+
+/// Returns a vault to its maker without running any of the regular refund logic, for use when
+/// that logic is broken. Only executable once the queued delay has passed.
+#[derive(Accounts)]
+pub struct EmergencyWithdraw<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = !escrow.is_streaming() @ EscrowError::AlreadyTaken,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"emergency_withdrawal", escrow.key().as_ref()],
+        bump = emergency_withdrawal.bump
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> EmergencyWithdraw<'info> {
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+        }
+        Ok(())
+    }
+
+    pub fn emergency_withdraw(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.emergency_withdrawal.execute_after,
+            EscrowError::TimelockNotElapsed
+        );
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.maker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        emit!(EmergencyWithdrawEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            amount: self.vault.amount,
+        });
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+}
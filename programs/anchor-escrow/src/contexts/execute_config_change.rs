@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError, PendingConfigChange};
+
+/// Permissionless once the delay has elapsed; rent is returned to the admin.
+#[derive(Accounts)]
+pub struct ExecuteConfigChange<'info> {
+    /// CHECK: Matched against `config.admin`; may be a program-owned PDA, only receives rent.
+    #[account(mut)]
+    pub admin: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        has_one = admin @ EscrowError::Unauthorized,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"pending_config_change"],
+        bump = pending_config_change.bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+}
+
+impl<'info> ExecuteConfigChange<'info> {
+    pub fn execute_config_change(&mut self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.pending_config_change.execute_after,
+            EscrowError::TimelockNotElapsed
+        );
+        require!(
+            self.config.params_hash()? == self.pending_config_change.config_hash,
+            EscrowError::ConfigChanged
+        );
+
+        self.config.apply(&self.pending_config_change.params);
+        Ok(())
+    }
+}
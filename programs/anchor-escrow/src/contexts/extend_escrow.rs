@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::{Escrow, EscrowError};
+
+/// Pushes back an escrow's expiry. Signed by the maker or their delegate. The config's duration
+/// cap still applies from `created_at`.
+#[derive(Accounts)]
+pub struct ExtendEscrow<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = escrow.is_manager(&authority.key()) @ EscrowError::Unauthorized,
+        constraint = !escrow.is_streaming() @ EscrowError::AlreadyTaken,
+        seeds = [b"escrow", escrow.maker.as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> ExtendEscrow<'info> {
+    pub fn extend_escrow(&mut self, expires_at: i64) -> Result<()> {
+        require!(
+            self.escrow.expires_at != 0 && expires_at > self.escrow.expires_at,
+            EscrowError::InvalidDuration
+        );
+        self.escrow.expires_at = expires_at;
+        self.escrow.record_update(Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+}
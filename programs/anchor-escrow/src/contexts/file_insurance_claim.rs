@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+use crate::{EscrowError, InsuranceClaim, InsurancePool};
+
+#[derive(Accounts)]
+#[instruction(escrow: Pubkey)]
+pub struct FileInsuranceClaim<'info> {
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+    #[account(
+        seeds = [b"insurance_pool", insurance_pool.mint.as_ref()],
+        bump = insurance_pool.bump
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + InsuranceClaim::INIT_SPACE,
+        seeds = [b"insurance_claim", escrow.as_ref(), claimant.key().as_ref()],
+        bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FileInsuranceClaim<'info> {
+    pub fn file_insurance_claim(
+        &mut self,
+        escrow: Pubkey,
+        amount: u64,
+        bumps: &FileInsuranceClaimBumps,
+    ) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidClaimAmount);
+
+        self.insurance_claim.set_inner(InsuranceClaim {
+            claimant: self.claimant.key(),
+            pool: self.insurance_pool.key(),
+            escrow,
+            amount,
+            bump: bumps.insurance_claim,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,239 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    Config, Escrow, EscrowError, FeeEpoch, FeeOverride, FeeStake, Fill, FillRecords, Leaderboard,
+    MakerDirectory, PairStats, TakeEvent,
+};
+
+/// Position of `escrow` in `FlashRepay`, which `flash_take` checks by introspection.
+pub const FLASH_REPAY_ESCROW_INDEX: usize = 2;
+
+/// Pays the maker for a deposit released by `flash_take` and closes the escrow.
+#[derive(Accounts)]
+pub struct FlashRepay<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !escrow.delegated @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+        constraint = !maker_ata_b.is_frozen() @ EscrowError::TokenAccountFrozen,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"fee_override", escrow.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+    /// The taker's staked protocol tokens, which discount the take fee by `config.stake_tiers`.
+    #[account(
+        seeds = [b"fee_stake", taker.key().as_ref()],
+        bump = fee_stake.bump
+    )]
+    pub fee_stake: Option<Box<Account<'info, FeeStake>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = config.treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Current epoch's bucket for mint_b fees paid to the treasury, required when there is a fee.
+    #[account(
+        mut,
+        seeds = [b"fee_epoch", mint_b.key().as_ref(), fee_epoch.epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch.bump
+    )]
+    pub fee_epoch: Option<Box<Account<'info, FeeEpoch>>>,
+    /// Ranks the maker by mint_b volume filled.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", mint_b.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = pair_stats.bump
+    )]
+    pub pair_stats: Option<Box<Account<'info, PairStats>>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FlashRepay<'info> {
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+        }
+        Ok(())
+    }
+
+    pub fn take_fee(&self) -> Result<u64> {
+        self.config.take_fee(
+            self.escrow.receive,
+            self.fee_override.as_deref(),
+            self.fee_stake.as_deref().map(|fee_stake| &**fee_stake),
+        )
+    }
+
+    pub fn deposit(&mut self) -> Result<()> {
+        let fee = self.take_fee()?;
+
+        if fee > 0 {
+            let treasury_ata_b = self
+                .treasury_ata_b
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.taker_ata_b.to_account_info(),
+                mint: self.mint_b.to_account_info(),
+                to: treasury_ata_b.to_account_info(),
+                authority: self.taker.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+            transfer_checked(cpi_ctx, fee, self.mint_b.decimals)?;
+        }
+
+        let transfer_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(
+            cpi_ctx,
+            self.escrow
+                .receive
+                .checked_sub(fee)
+                .ok_or(EscrowError::Overflow)?,
+            self.mint_b.decimals,
+        )
+    }
+
+    pub fn record_fill(&mut self) -> Result<()> {
+        let fill = Fill {
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            amount: self.escrow.amount,
+            receive: self.escrow.receive,
+            treasury_fee: self.take_fee()?,
+            rebate: 0,
+        };
+
+        FillRecords {
+            pair_stats: self
+                .pair_stats
+                .as_deref_mut()
+                .map(|pair_stats| &mut **pair_stats),
+            fee_epoch: self
+                .fee_epoch
+                .as_deref_mut()
+                .map(|fee_epoch| &mut **fee_epoch),
+            leaderboard: self
+                .leaderboard
+                .as_deref_mut()
+                .map(|leaderboard| &mut **leaderboard),
+            ..Default::default()
+        }
+        .record(&fill)
+    }
+
+    /// The vault was emptied by `flash_take`, so it closes without a transfer.
+    pub fn close_vault(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        emit!(TakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            amount: self.escrow.amount,
+            receive: self.escrow.receive,
+            fee: self.take_fee()?,
+            protocol_fee: 0,
+            referrer: None,
+            referral_fee: 0,
+            donation: 0,
+            frontend_fee: 0,
+            note: self.escrow.note,
+        });
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.taker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+}
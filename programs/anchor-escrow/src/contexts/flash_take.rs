@@ -0,0 +1,136 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions::{
+        self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+    },
+    Discriminator,
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    accepts_public_credits, instruction::FlashRepay, Config, Escrow, EscrowError,
+    FLASH_REPAY_ESCROW_INDEX,
+};
+
+/// Sends the deposit to the taker before they pay, provided a `flash_repay` for the same escrow
+/// follows later in the transaction. If the repayment fails, the whole transaction reverts.
+#[derive(Accounts)]
+pub struct FlashTake<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    pub maker: SystemAccount<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = taker,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+        constraint = !taker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&taker_ata_a.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        has_one = maker,
+        has_one = mint_a,
+        constraint = !escrow.delegated && !escrow.unwrap_sol && !escrow.take_only() && !escrow.is_scheduled() @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    /// CHECK: instructions sysvar
+    #[account(address = instructions_sysvar::ID)]
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub instructions: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> FlashTake<'info> {
+    pub fn check_active(&self) -> Result<()> {
+        self.config
+            .check_cpi_caller(Some(self.instructions.as_ref()))?;
+        self.escrow.check_takeable(
+            &self.taker.key(),
+            Clock::get()?.unix_timestamp,
+            &self.config,
+        )
+    }
+
+    /// Looks for a `flash_repay` of this escrow after the current instruction.
+    pub fn check_repayment(&self) -> Result<()> {
+        let instructions = self.instructions.to_account_info();
+        let current = load_current_index_checked(&instructions)? as usize;
+
+        let mut index = current + 1;
+        while let Ok(ix) = load_instruction_at_checked(index, &instructions) {
+            if ix.program_id == crate::ID
+                && ix.data.get(..8) == Some(&FlashRepay::DISCRIMINATOR[..])
+                && ix
+                    .accounts
+                    .get(FLASH_REPAY_ESCROW_INDEX)
+                    .is_some_and(|meta| meta.pubkey == self.escrow.key())
+            {
+                return Ok(());
+            }
+            index += 1;
+        }
+
+        err!(EscrowError::MissingRepayment)
+    }
+
+    pub fn withdraw(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, Escrow, EscrowError, FreezeEvent};
+
+#[derive(Accounts)]
+pub struct FreezeEscrow<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub escrow: Account<'info, Escrow>,
+}
+
+impl<'info> FreezeEscrow<'info> {
+    pub fn set_frozen(&mut self, frozen: bool) -> Result<()> {
+        self.escrow.frozen = frozen;
+
+        emit!(FreezeEvent {
+            escrow: self.escrow.key(),
+            frozen,
+        });
+        Ok(())
+    }
+}
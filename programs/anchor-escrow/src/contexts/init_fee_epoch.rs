@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::FeeEpoch;
+
+#[derive(Accounts)]
+#[instruction(epoch: u64)]
+pub struct InitFeeEpoch<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + FeeEpoch::INIT_SPACE,
+        seeds = [b"fee_epoch", mint.key().as_ref(), epoch.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub fee_epoch: Account<'info, FeeEpoch>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitFeeEpoch<'info> {
+    pub fn init_fee_epoch(&mut self, epoch: u64, bumps: &InitFeeEpochBumps) -> Result<()> {
+        self.fee_epoch.set_inner(FeeEpoch {
+            mint: self.mint.key(),
+            epoch,
+            fees: 0,
+            fills: 0,
+            bump: bumps.fee_epoch,
+        });
+        Ok(())
+    }
+}
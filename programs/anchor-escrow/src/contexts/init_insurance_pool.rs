@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::{Config, EscrowError, InsurancePool};
+
+#[derive(Accounts)]
+pub struct InitInsurancePool<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + InsurancePool::INIT_SPACE,
+        seeds = [b"insurance_pool", mint.key().as_ref()],
+        bump
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint,
+        associated_token::authority = insurance_pool,
+        associated_token::token_program = token_program
+    )]
+    pub insurance_vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitInsurancePool<'info> {
+    pub fn init_insurance_pool(&mut self, bumps: &InitInsurancePoolBumps) -> Result<()> {
+        self.insurance_pool.set_inner(InsurancePool {
+            mint: self.mint.key(),
+            premiums: 0,
+            claims_paid: 0,
+            bump: bumps.insurance_pool,
+        });
+        Ok(())
+    }
+}
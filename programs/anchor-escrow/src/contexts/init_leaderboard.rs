@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::Leaderboard;
+
+#[derive(Accounts)]
+pub struct InitLeaderboard<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Leaderboard::INIT_SPACE,
+        seeds = [b"leaderboard", mint.key().as_ref()],
+        bump
+    )]
+    pub leaderboard: Account<'info, Leaderboard>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitLeaderboard<'info> {
+    pub fn init_leaderboard(&mut self, bumps: &InitLeaderboardBumps) -> Result<()> {
+        self.leaderboard.set_inner(Leaderboard {
+            mint: self.mint.key(),
+            entries: vec![],
+            bump: bumps.leaderboard,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::MakerCounter;
+
+#[derive(Accounts)]
+pub struct InitMakerCounter<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + MakerCounter::INIT_SPACE,
+        seeds = [b"maker_counter", maker.key().as_ref()],
+        bump
+    )]
+    pub maker_counter: Account<'info, MakerCounter>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitMakerCounter<'info> {
+    pub fn init_maker_counter(&mut self, bumps: &InitMakerCounterBumps) -> Result<()> {
+        self.maker_counter.set_inner(MakerCounter {
+            maker: self.maker.key(),
+            count: 0,
+            bump: bumps.maker_counter,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::MakerDirectory;
+
+#[derive(Accounts)]
+pub struct InitMakerDirectory<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + MakerDirectory::INIT_SPACE,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump
+    )]
+    pub maker_directory: Account<'info, MakerDirectory>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitMakerDirectory<'info> {
+    pub fn init_maker_directory(&mut self, bumps: &InitMakerDirectoryBumps) -> Result<()> {
+        self.maker_directory.set_inner(MakerDirectory {
+            maker: self.maker.key(),
+            seeds: vec![],
+            bump: bumps.maker_directory,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::OfferTree;
+
+#[derive(Accounts)]
+#[instruction(id: u32)]
+pub struct InitOfferTree<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<OfferTree>(),
+        seeds = [b"offer_tree", id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub offer_tree: AccountLoader<'info, OfferTree>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitOfferTree<'info> {
+    pub fn init_offer_tree(&mut self, id: u32, bumps: &InitOfferTreeBumps) -> Result<()> {
+        self.offer_tree
+            .load_init()?
+            .initialize(id, bumps.offer_tree);
+        Ok(())
+    }
+}
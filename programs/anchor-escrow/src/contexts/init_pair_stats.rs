@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::PairStats;
+
+#[derive(Accounts)]
+pub struct InitPairStats<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PairStats::INIT_SPACE,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub pair_stats: Account<'info, PairStats>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitPairStats<'info> {
+    pub fn init_pair_stats(&mut self, bumps: &InitPairStatsBumps) -> Result<()> {
+        self.pair_stats.set_inner(PairStats {
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            fills: 0,
+            volume_a: 0,
+            volume_b: 0,
+            bump: bumps.pair_stats,
+            reserved: [0; 64],
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::Points;
+
+#[derive(Accounts)]
+pub struct InitPoints<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Points::INIT_SPACE,
+        seeds = [b"points", owner.key().as_ref()],
+        bump
+    )]
+    pub points: Account<'info, Points>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitPoints<'info> {
+    pub fn init_points(&mut self, bumps: &InitPointsBumps) -> Result<()> {
+        self.points.set_inner(Points {
+            owner: self.owner.key(),
+            points: 0,
+            bump: bumps.points,
+        });
+        Ok(())
+    }
+}
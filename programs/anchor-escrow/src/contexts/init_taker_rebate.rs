@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::TakerRebate;
+
+#[derive(Accounts)]
+pub struct InitTakerRebate<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = taker,
+        space = 8 + TakerRebate::INIT_SPACE,
+        seeds = [b"taker_rebate", taker.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub taker_rebate: Account<'info, TakerRebate>,
+    #[account(
+        init,
+        payer = taker,
+        associated_token::mint = mint,
+        associated_token::authority = taker_rebate,
+        associated_token::token_program = token_program
+    )]
+    pub rebate_vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitTakerRebate<'info> {
+    pub fn init_taker_rebate(&mut self, bumps: &InitTakerRebateBumps) -> Result<()> {
+        self.taker_rebate.set_inner(TakerRebate {
+            taker: self.taker.key(),
+            mint: self.mint.key(),
+            volume: 0,
+            accrued: 0,
+            bump: bumps.taker_rebate,
+        });
+        Ok(())
+    }
+}
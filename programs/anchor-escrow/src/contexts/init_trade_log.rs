@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::TradeLog;
+
+#[derive(Accounts)]
+pub struct InitTradeLog<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + std::mem::size_of::<TradeLog>(),
+        seeds = [b"trade_log", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub trade_log: AccountLoader<'info, TradeLog>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitTradeLog<'info> {
+    pub fn init_trade_log(&mut self, bumps: &InitTradeLogBumps) -> Result<()> {
+        let mut trade_log = self.trade_log.load_init()?;
+        trade_log.mint_a = self.mint_a.key();
+        trade_log.mint_b = self.mint_b.key();
+        trade_log.bump = bumps.trade_log;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    program::AnchorEscrow, Config, ConfigParams, EscrowError, StakeTier, MAX_CPI_CALLERS,
+    MAX_STAKE_TIERS,
+};
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::INIT_SPACE,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        constraint = program.programdata_address()? == Some(program_data.key())
+    )]
+    pub program: Program<'info, AnchorEscrow>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ EscrowError::Unauthorized
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeConfig<'info> {
+    pub fn initialize_config(
+        &mut self,
+        params: ConfigParams,
+        bumps: &InitializeConfigBumps,
+    ) -> Result<()> {
+        params.validate()?;
+        self.config.set_inner(Config {
+            admin: self.admin.key(),
+            pending_admin: Pubkey::default(),
+            treasury: Pubkey::default(),
+            creation_fee: 0,
+            cancellation_fee: 0,
+            cancellation_fee_bps: 0,
+            max_duration: 0,
+            min_deposit: 0,
+            max_receive: 0,
+            take_fee_bps: 0,
+            protocol_mint: Pubkey::default(),
+            protocol_fee_discount_bps: 0,
+            burn_protocol_fee: false,
+            referral_share_bps: 0,
+            timelock: 0,
+            admin_cpi_only: false,
+            reject_freezable_mints: false,
+            match_incentive_bps: 0,
+            auto_refund_tip: 0,
+            max_creations_per_epoch: 0,
+            min_deposit_tokens: 0,
+            dust_threshold: 0,
+            cpi_allowlist: [Pubkey::default(); MAX_CPI_CALLERS],
+            bump: bumps.config,
+            compliance_signer: Pubkey::default(),
+            max_frontend_fee_bps: 0,
+            insurance_premium_bps: 0,
+            stake_tiers: [StakeTier::default(); MAX_STAKE_TIERS],
+            reserved: [0; 64],
+        });
+        self.config.apply(&params);
+        Ok(())
+    }
+}
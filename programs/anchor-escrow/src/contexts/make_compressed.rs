@@ -0,0 +1,162 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    require_transferable, CompressedMakeEvent, CompressedOffer, Config, EscrowError, MakerActivity,
+    OfferTree,
+};
+
+/// Posts an offer as a leaf of `offer_tree` instead of a rent-exempt escrow account. The config's
+/// make limits apply as for `make`; compressed offers can't expire, so a `max_duration` cap rules
+/// them out. The leaf records what the pooled vault actually received, net of transfer fees.
+#[derive(Accounts)]
+pub struct MakeCompressed<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub offer_tree: AccountLoader<'info, OfferTree>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = offer_tree,
+        associated_token::token_program = token_program
+    )]
+    pub tree_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        space = 8 + MakerActivity::INIT_SPACE,
+        seeds = [b"maker_activity", maker.key().as_ref()],
+        bump
+    )]
+    pub maker_activity: Account<'info, MakerActivity>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeCompressed<'info> {
+    pub fn validate(
+        &mut self,
+        amount: u64,
+        receive: u64,
+        bumps: &MakeCompressedBumps,
+    ) -> Result<()> {
+        self.config
+            .validate_terms(amount, receive, 0, Clock::get()?.unix_timestamp)?;
+        self.config
+            .validate_deposit_tokens(amount, self.mint_a.decimals)?;
+        self.config.validate_mint(&self.mint_a)?;
+        self.config.validate_mint(&self.mint_b)?;
+        require_transferable(&self.mint_a.to_account_info())?;
+        require_transferable(&self.mint_b.to_account_info())?;
+
+        self.maker_activity.maker = self.maker.key();
+        self.maker_activity.bump = bumps.maker_activity;
+        self.maker_activity
+            .record_creation(Clock::get()?.epoch, self.config.max_creations_per_epoch)
+    }
+
+    pub fn pay_creation_fee(&mut self) -> Result<()> {
+        if self.config.creation_fee == 0 {
+            return Ok(());
+        }
+
+        let transfer_accounts = Transfer {
+            from: self.maker.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, self.config.creation_fee)
+    }
+
+    pub fn append_offer(&mut self, amount: u64, receive: u64) -> Result<()> {
+        let offer = CompressedOffer {
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount,
+            receive,
+        };
+        let index = self.offer_tree.load_mut()?.append(&offer)?;
+
+        emit!(CompressedMakeEvent {
+            offer_tree: self.offer_tree.key(),
+            index,
+            maker: offer.maker,
+            mint_a: offer.mint_a,
+            mint_b: offer.mint_b,
+            amount,
+            receive,
+        });
+        Ok(())
+    }
+
+    /// Returns the amount the pooled vault received, which is what the offer can pay out without
+    /// drawing on other makers' deposits.
+    pub fn deposit(&mut self, amount: u64) -> Result<u64> {
+        let before = self.tree_vault.amount;
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.tree_vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint_a.decimals)?;
+
+        self.tree_vault.reload()?;
+        let received = self
+            .tree_vault
+            .amount
+            .checked_sub(before)
+            .ok_or(EscrowError::Overflow)?;
+        require!(received > 0, EscrowError::DepositTooSmall);
+        Ok(received)
+    }
+}
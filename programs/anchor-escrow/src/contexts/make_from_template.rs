@@ -0,0 +1,237 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    is_native_mint, require_transferable, validate_deposit_mint, Config, Escrow, EscrowError,
+    MakeEvent, MakerActivity, MakerCounter, MakerDirectory, Template,
+};
+
+/// Posts an escrow on a template's mints, price ratio, duration and flags; only the seed and
+/// deposit come from the instruction.
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct MakeFromTemplate<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        has_one = mint_a,
+        has_one = mint_b,
+        seeds = [b"template", template.owner.as_ref(), template.id.to_le_bytes().as_ref()],
+        bump = template.bump
+    )]
+    pub template: Account<'info, Template>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), seed.as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    /// Holds the public balance leg for confidential transfer mints; makers must withdraw
+    /// confidential balances to public before depositing.
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = !vault.is_frozen() @ EscrowError::TokenAccountFrozen
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// When present, `seed` must equal `maker_counter.next_seed()`, after which the counter is incremented.
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [b"maker_counter", maker.key().as_ref()],
+        bump = maker_counter.bump
+    )]
+    pub maker_counter: Option<Account<'info, MakerCounter>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MakerActivity::INIT_SPACE,
+        seeds = [b"maker_activity", maker.key().as_ref()],
+        bump
+    )]
+    pub maker_activity: Account<'info, MakerActivity>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeFromTemplate<'info> {
+    pub fn pay_creation_fee(&mut self) -> Result<()> {
+        if self.config.creation_fee == 0 {
+            return Ok(());
+        }
+
+        let transfer_accounts = Transfer {
+            from: self.maker.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, self.config.creation_fee)
+    }
+
+    pub fn save_escrow(
+        &mut self,
+        seed: [u8; 32],
+        deposit: u64,
+        bumps: &MakeFromTemplateBumps,
+    ) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        let receive = self.template.receive_for(deposit)?;
+        let params = self.template.params(now);
+        self.config.validate_make(deposit, receive, &params, now)?;
+        self.config
+            .validate_deposit_tokens(deposit, self.mint_a.decimals)?;
+        self.config.validate_mint(&self.mint_a)?;
+        self.config.validate_mint(&self.mint_b)?;
+        validate_deposit_mint(&self.mint_a.to_account_info(), &params)?;
+        require_transferable(&self.mint_a.to_account_info())?;
+        require_transferable(&self.mint_b.to_account_info())?;
+        require!(
+            !params.unwrap_sol || is_native_mint(&self.mint_b.key()),
+            EscrowError::NotNativeMint
+        );
+
+        self.maker_activity.maker = self.maker.key();
+        self.maker_activity.bump = bumps.maker_activity;
+        self.maker_activity
+            .record_creation(Clock::get()?.epoch, self.config.max_creations_per_epoch)?;
+
+        if let Some(maker_counter) = self.maker_counter.as_mut() {
+            require!(seed == maker_counter.next_seed(), EscrowError::InvalidSeed);
+            maker_counter.count = maker_counter
+                .count
+                .checked_add(1)
+                .ok_or(EscrowError::Overflow)?;
+        }
+
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.insert(seed)?;
+        }
+
+        self.escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount: deposit,
+            receive,
+            delegated: false,
+            note: params.note,
+            metadata_uri: params.metadata_uri,
+            locked_until: params.locked_until,
+            starts_at: params.starts_at,
+            expires_at: params.expires_at,
+            created_at: now,
+            frozen: false,
+            bump: bumps.escrow,
+            version: Escrow::VERSION,
+            unwrap_sol: params.unwrap_sol,
+            payment_options: params.payment_options,
+            allow_partial_fills: params.allow_partial_fills,
+            price_tiers: params.price_tiers,
+            stream_duration: params.stream_duration,
+            stream_taker: Pubkey::default(),
+            stream_started_at: 0,
+            streamed: 0,
+            delegate: params.delegate,
+            reject_cpi: params.reject_cpi,
+            allowed_takers: params.allowed_takers,
+            sealed_bids: params.sealed_bids,
+            payout_splits: params.payout_splits,
+            donation_recipient: params.donation_recipient,
+            donation_bps: params.donation_bps,
+            relist_receive: params.relist_receive,
+            condition: params.condition,
+            tranche: params.tranche,
+            fill_interval: params.fill_interval,
+            last_fill_ts: 0,
+            co_signer: params.co_signer,
+            require_compliance: params.require_compliance,
+            credential: params.credential,
+            frontend: params.frontend,
+            frontend_fee_bps: params.frontend_fee_bps,
+            vault: self.vault.key(),
+            last_updated_at: 0,
+            revision: 0,
+            reserved: [0; 64],
+        });
+
+        emit!(MakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            deposit,
+            receive,
+            note: params.note,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, deposit: u64) -> Result<()> {
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)
+    }
+}
@@ -0,0 +1,88 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke,
+        stake::{self, state::StakeAuthorize},
+    },
+};
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{EscrowError, StakeEscrow};
+
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct MakeStake<'info> {
+    /// Current staker and withdrawer of `stake_account`.
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    /// CHECK: the stake program checks the current authorities when they are reassigned.
+    #[account(
+        mut,
+        owner = stake::program::ID
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + StakeEscrow::INIT_SPACE,
+        seeds = [b"stake_escrow", maker.key().as_ref(), seed.as_ref()],
+        bump
+    )]
+    pub stake_escrow: Account<'info, StakeEscrow>,
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: the stake program.
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeStake<'info> {
+    pub fn save_stake_escrow(
+        &mut self,
+        seed: [u8; 32],
+        receive: u64,
+        bumps: &MakeStakeBumps,
+    ) -> Result<()> {
+        self.stake_escrow.set_inner(StakeEscrow {
+            seed,
+            maker: self.maker.key(),
+            stake: self.stake_account.key(),
+            mint_b: self.mint_b.key(),
+            receive,
+            bump: bumps.stake_escrow,
+        });
+        Ok(())
+    }
+
+    pub fn assign_authorities(&mut self) -> Result<()> {
+        // Staker first, as the withdrawer authority is needed to move either.
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            let ix = stake::instruction::authorize(
+                self.stake_account.key,
+                &self.maker.key(),
+                &self.stake_escrow.key(),
+                stake_authorize,
+                None,
+            );
+
+            invoke(
+                &ix,
+                &[
+                    self.stake_account.to_account_info(),
+                    self.clock.to_account_info(),
+                    self.maker.to_account_info(),
+                ],
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{require_transferable, EscrowError, Subscription};
+
+/// Pre-funds `periods` tranches of `amount_per_period` for `taker` to claim one period at a time.
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct MakeSubscription<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    pub taker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Subscription::INIT_SPACE,
+        seeds = [b"subscription", maker.key().as_ref(), seed.as_ref()],
+        bump
+    )]
+    pub subscription: Account<'info, Subscription>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint,
+        associated_token::authority = subscription,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeSubscription<'info> {
+    pub fn save_subscription(
+        &mut self,
+        seed: [u8; 32],
+        amount_per_period: u64,
+        period: i64,
+        starts_at: i64,
+        periods: u32,
+        bumps: &MakeSubscriptionBumps,
+    ) -> Result<()> {
+        require!(period > 0 && periods > 0, EscrowError::InvalidDuration);
+        require_transferable(&self.mint.to_account_info())?;
+
+        self.subscription.set_inner(Subscription {
+            seed,
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            mint: self.mint.key(),
+            amount_per_period,
+            period,
+            starts_at,
+            periods,
+            claimed: 0,
+            bump: bumps.subscription,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self) -> Result<()> {
+        let amount = self
+            .subscription
+            .amount_per_period
+            .checked_mul(self.subscription.periods as u64)
+            .ok_or(EscrowError::Overflow)?;
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata.to_account_info(),
+            mint: self.mint.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.mint.decimals)
+    }
+}
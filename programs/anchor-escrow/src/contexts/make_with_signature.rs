@@ -0,0 +1,356 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        ed25519_program,
+        program_option::COption,
+        sysvar::instructions::{
+            self as instructions_sysvar, load_current_index_checked, load_instruction_at_checked,
+        },
+    },
+    system_program::{transfer, Transfer},
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    require_transferable, validate_deposit_mint, Config, CredentialGate, Escrow, EscrowError,
+    EscrowParams, MakeEvent, MakerActivity, MakerCounter, MakerDirectory, PaymentOption,
+    PayoutSplit, PriceTier, TakeCondition, MAX_ALLOWED_TAKERS, MAX_PAYMENT_OPTIONS,
+    MAX_PAYOUT_SPLITS, MAX_PRICE_TIERS,
+};
+
+/// Terms the maker signs off-chain for `make_with_signature`. All other escrow options are left
+/// off so the signed message and the transaction stay small.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SignedTerms {
+    /// Must equal `maker_counter.next_seed()`, which makes every signature single-use.
+    pub seed: [u8; 32],
+    pub deposit: u64,
+    pub receive: u64,
+    pub expires_at: i64,
+    /// Unix timestamp after which the signature can no longer be submitted.
+    pub deadline: i64,
+}
+
+impl SignedTerms {
+    /// Bytes the maker signs: the program ID, maker and mints followed by the terms.
+    pub fn message(&self, maker: &Pubkey, mint_a: &Pubkey, mint_b: &Pubkey) -> Result<Vec<u8>> {
+        let mut message = Vec::with_capacity(160);
+        (crate::ID, *maker, *mint_a, *mint_b, self).serialize(&mut message)?;
+        Ok(message)
+    }
+}
+
+/// Layout of an Ed25519 program instruction verifying one signature whose key, signature and
+/// message are all stored in the instruction itself.
+const ED25519_OFFSETS_START: usize = 2;
+const ED25519_PUBKEY_OFFSET: usize = ED25519_OFFSETS_START + 14;
+const ED25519_SIGNATURE_OFFSET: usize = ED25519_PUBKEY_OFFSET + 32;
+const ED25519_MESSAGE_OFFSET: usize = ED25519_SIGNATURE_OFFSET + 64;
+
+/// Makes an escrow from terms the maker signed off-chain. The relayer submits the transaction
+/// after an Ed25519 program instruction that verifies the maker's signature, and pays rent and
+/// the creation fee. The deposit is pulled through the `make_authority` PDA, which the maker must
+/// have approved as delegate of `maker_ata_a` beforehand.
+#[derive(Accounts)]
+#[instruction(terms: SignedTerms)]
+pub struct MakeWithSignature<'info> {
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+    pub maker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+        constraint = maker_ata_a.delegate == COption::Some(make_authority.key()) @ EscrowError::Unauthorized
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: PDA that only signs deposits for makes the maker has signed.
+    #[account(
+        seeds = [b"make_authority", maker.key().as_ref()],
+        bump
+    )]
+    pub make_authority: UncheckedAccount<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        address = config.treasury
+    )]
+    pub treasury: SystemAccount<'info>,
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), terms.seed.as_ref()],
+        bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = relayer,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = !vault.is_frozen() @ EscrowError::TokenAccountFrozen
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + MakerCounter::INIT_SPACE,
+        seeds = [b"maker_counter", maker.key().as_ref()],
+        bump
+    )]
+    pub maker_counter: Account<'info, MakerCounter>,
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + MakerActivity::INIT_SPACE,
+        seeds = [b"maker_activity", maker.key().as_ref()],
+        bump
+    )]
+    pub maker_activity: Account<'info, MakerActivity>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    /// CHECK: instructions sysvar, read to find the Ed25519 verification.
+    #[account(address = instructions_sysvar::ID)]
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub instructions: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MakeWithSignature<'info> {
+    /// Requires the instruction just before this one to verify the maker's signature over
+    /// `terms`, with the key, signature and message all inside that instruction.
+    pub fn check_signature(&self, terms: &SignedTerms) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp <= terms.deadline,
+            EscrowError::SignatureExpired
+        );
+
+        let instructions = self.instructions.to_account_info();
+        let current = load_current_index_checked(&instructions)? as usize;
+        let ix = current
+            .checked_sub(1)
+            .map(|index| load_instruction_at_checked(index, &instructions))
+            .transpose()?
+            .ok_or(EscrowError::InvalidMakerSignature)?;
+
+        let message = terms.message(&self.maker.key(), &self.mint_a.key(), &self.mint_b.key())?;
+        let offsets = [
+            ED25519_SIGNATURE_OFFSET as u16,
+            u16::MAX,
+            ED25519_PUBKEY_OFFSET as u16,
+            u16::MAX,
+            ED25519_MESSAGE_OFFSET as u16,
+            message.len() as u16,
+            u16::MAX,
+        ];
+        let mut expected = vec![1, 0];
+        expected.extend(offsets.iter().flat_map(|offset| offset.to_le_bytes()));
+
+        require!(
+            ix.program_id == ed25519_program::ID
+                && ix.data.len() == ED25519_MESSAGE_OFFSET + message.len()
+                && ix.data[..ED25519_PUBKEY_OFFSET] == expected[..]
+                && ix.data[ED25519_PUBKEY_OFFSET..ED25519_SIGNATURE_OFFSET]
+                    == self.maker.key().to_bytes()
+                && ix.data[ED25519_MESSAGE_OFFSET..] == message[..],
+            EscrowError::InvalidMakerSignature
+        );
+        Ok(())
+    }
+
+    pub fn pay_creation_fee(&mut self) -> Result<()> {
+        if self.config.creation_fee == 0 {
+            return Ok(());
+        }
+
+        let transfer_accounts = Transfer {
+            from: self.relayer.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, self.config.creation_fee)
+    }
+
+    pub fn save_escrow(
+        &mut self,
+        terms: &SignedTerms,
+        bumps: &MakeWithSignatureBumps,
+    ) -> Result<()> {
+        let params = EscrowParams {
+            note: [0; 64],
+            metadata_uri: [0; 128],
+            locked_until: 0,
+            starts_at: 0,
+            expires_at: terms.expires_at,
+            allow_permanent_delegate: false,
+            unwrap_sol: false,
+            payment_options: [PaymentOption::default(); MAX_PAYMENT_OPTIONS],
+            allow_partial_fills: false,
+            price_tiers: [PriceTier::default(); MAX_PRICE_TIERS],
+            stream_duration: 0,
+            delegate: Pubkey::default(),
+            reject_cpi: false,
+            allowed_takers: [Pubkey::default(); MAX_ALLOWED_TAKERS],
+            sealed_bids: false,
+            payout_splits: [PayoutSplit::default(); MAX_PAYOUT_SPLITS],
+            donation_recipient: Pubkey::default(),
+            donation_bps: 0,
+            relist_receive: 0,
+            condition: TakeCondition::default(),
+            tranche: 0,
+            fill_interval: 0,
+            co_signer: Pubkey::default(),
+            require_compliance: false,
+            credential: CredentialGate::default(),
+            frontend: Pubkey::default(),
+            frontend_fee_bps: 0,
+        };
+
+        let now = Clock::get()?.unix_timestamp;
+        self.config
+            .validate_make(terms.deposit, terms.receive, &params, now)?;
+        self.config
+            .validate_deposit_tokens(terms.deposit, self.mint_a.decimals)?;
+        self.config.validate_mint(&self.mint_a)?;
+        self.config.validate_mint(&self.mint_b)?;
+        validate_deposit_mint(&self.mint_a.to_account_info(), &params)?;
+        require_transferable(&self.mint_a.to_account_info())?;
+        require_transferable(&self.mint_b.to_account_info())?;
+
+        self.maker_activity.maker = self.maker.key();
+        self.maker_activity.bump = bumps.maker_activity;
+        self.maker_activity
+            .record_creation(Clock::get()?.epoch, self.config.max_creations_per_epoch)?;
+
+        self.maker_counter.maker = self.maker.key();
+        self.maker_counter.bump = bumps.maker_counter;
+        require!(
+            terms.seed == self.maker_counter.next_seed(),
+            EscrowError::InvalidSeed
+        );
+        self.maker_counter.count = self
+            .maker_counter
+            .count
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.insert(terms.seed)?;
+        }
+
+        self.escrow.set_inner(Escrow {
+            seed: terms.seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount: terms.deposit,
+            receive: terms.receive,
+            delegated: false,
+            note: params.note,
+            metadata_uri: params.metadata_uri,
+            locked_until: params.locked_until,
+            starts_at: params.starts_at,
+            expires_at: params.expires_at,
+            created_at: now,
+            frozen: false,
+            bump: bumps.escrow,
+            version: Escrow::VERSION,
+            unwrap_sol: params.unwrap_sol,
+            payment_options: params.payment_options,
+            allow_partial_fills: params.allow_partial_fills,
+            price_tiers: params.price_tiers,
+            stream_duration: params.stream_duration,
+            stream_taker: Pubkey::default(),
+            stream_started_at: 0,
+            streamed: 0,
+            delegate: params.delegate,
+            reject_cpi: params.reject_cpi,
+            allowed_takers: params.allowed_takers,
+            sealed_bids: params.sealed_bids,
+            payout_splits: params.payout_splits,
+            donation_recipient: params.donation_recipient,
+            donation_bps: params.donation_bps,
+            relist_receive: params.relist_receive,
+            condition: params.condition,
+            tranche: params.tranche,
+            fill_interval: params.fill_interval,
+            last_fill_ts: 0,
+            co_signer: params.co_signer,
+            require_compliance: params.require_compliance,
+            credential: params.credential,
+            frontend: params.frontend,
+            frontend_fee_bps: params.frontend_fee_bps,
+            vault: self.vault.key(),
+            last_updated_at: 0,
+            revision: 0,
+            reserved: [0; 64],
+        });
+
+        emit!(MakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            deposit: terms.deposit,
+            receive: terms.receive,
+            note: params.note,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, deposit: u64, bumps: &MakeWithSignatureBumps) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"make_authority",
+            self.maker.to_account_info().key.as_ref(),
+            &[bumps.make_authority],
+        ]];
+
+        let transfer_accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.make_authority.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            transfer_accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(cpi_ctx, deposit, self.mint_a.decimals)
+    }
+}
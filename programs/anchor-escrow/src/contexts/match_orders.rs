@@ -0,0 +1,527 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    bps_of, Config, Escrow, EscrowError, FeeEpoch, FeeOverride, FeeStake, Fill, FillRecords,
+    Keeper, Leaderboard, MakerDirectory, MatchEvent, PairStats,
+};
+
+/// Settles two escrows trading the same pair in opposite directions against each other. Each maker
+/// receives their ask less the take fee, as if taken, and gets back whatever of their own deposit
+/// the other side didn't ask for, less `config.match_incentive_bps` of it for the matcher.
+#[derive(Accounts)]
+pub struct MatchOrders<'info> {
+    #[account(mut)]
+    pub matcher: Signer<'info>,
+    #[account(mut)]
+    pub maker_a: SystemAccount<'info>,
+    #[account(mut)]
+    pub maker_b: SystemAccount<'info>,
+    pub mint_x: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        constraint = mint_y.key() != mint_x.key() @ EscrowError::PricesDoNotCross
+    )]
+    pub mint_y: Box<InterfaceAccount<'info, Mint>>,
+    /// Sells mint_x for mint_y.
+    #[account(
+        mut,
+        close = maker_a,
+        constraint = escrow_a.maker == maker_a.key() @ EscrowError::Unauthorized,
+        constraint = escrow_a.mint_a == mint_x.key() && escrow_a.mint_b == mint_y.key() @ EscrowError::PricesDoNotCross,
+        constraint = !escrow_a.delegated && !escrow_a.take_only() && !escrow_a.is_scheduled() @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker_a.key().as_ref(), escrow_a.seed.as_ref()],
+        bump = escrow_a.bump
+    )]
+    pub escrow_a: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = escrow_a,
+        associated_token::token_program = token_program,
+        constraint = escrow_a.holds_vault(&vault_a.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// Sells mint_y for mint_x.
+    #[account(
+        mut,
+        close = maker_b,
+        constraint = escrow_b.maker == maker_b.key() @ EscrowError::Unauthorized,
+        constraint = escrow_b.mint_a == mint_y.key() && escrow_b.mint_b == mint_x.key() @ EscrowError::PricesDoNotCross,
+        constraint = !escrow_b.delegated && !escrow_b.take_only() && !escrow_b.is_scheduled() @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker_b.key().as_ref(), escrow_b.seed.as_ref()],
+        bump = escrow_b.bump
+    )]
+    pub escrow_b: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = escrow_b,
+        associated_token::token_program = token_program,
+        constraint = escrow_b.holds_vault(&vault_b.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = mint_x,
+        associated_token::authority = maker_a,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_a_ata_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = mint_y,
+        associated_token::authority = maker_a,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_a_ata_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = mint_x,
+        associated_token::authority = maker_b,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_b_ata_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = mint_y,
+        associated_token::authority = maker_b,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_b_ata_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = mint_x,
+        associated_token::authority = matcher,
+        associated_token::token_program = token_program,
+    )]
+    pub matcher_ata_x: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = matcher,
+        associated_token::mint = mint_y,
+        associated_token::authority = matcher,
+        associated_token::token_program = token_program,
+    )]
+    pub matcher_ata_y: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"fee_override", escrow_a.key().as_ref()],
+        bump = fee_override_a.bump
+    )]
+    pub fee_override_a: Option<Box<Account<'info, FeeOverride>>>,
+    #[account(
+        seeds = [b"fee_override", escrow_b.key().as_ref()],
+        bump = fee_override_b.bump
+    )]
+    pub fee_override_b: Option<Box<Account<'info, FeeOverride>>>,
+    /// maker_a's staked protocol tokens, which discount the take fee on escrow_b, the escrow maker_a fills.
+    #[account(
+        seeds = [b"fee_stake", maker_a.key().as_ref()],
+        bump = fee_stake_a.bump
+    )]
+    pub fee_stake_a: Option<Box<Account<'info, FeeStake>>>,
+    /// maker_b's staked protocol tokens, which discount the take fee on escrow_a, the escrow maker_b fills.
+    #[account(
+        seeds = [b"fee_stake", maker_b.key().as_ref()],
+        bump = fee_stake_b.bump
+    )]
+    pub fee_stake_b: Option<Box<Account<'info, FeeStake>>>,
+    /// Receives the take fee on maker_b's proceeds.
+    #[account(
+        mut,
+        associated_token::mint = mint_x,
+        associated_token::authority = config.treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_x: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Current epoch's bucket for mint_x fees paid to the treasury, required when `fee_b` is set.
+    #[account(
+        mut,
+        seeds = [b"fee_epoch", mint_x.key().as_ref(), fee_epoch_x.epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch_x.bump
+    )]
+    pub fee_epoch_x: Option<Box<Account<'info, FeeEpoch>>>,
+    /// Receives the take fee on maker_a's proceeds.
+    #[account(
+        mut,
+        associated_token::mint = mint_y,
+        associated_token::authority = config.treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_y: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Current epoch's bucket for mint_y fees paid to the treasury, required when `fee_a` is set.
+    #[account(
+        mut,
+        seeds = [b"fee_epoch", mint_y.key().as_ref(), fee_epoch_y.epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch_y.bump
+    )]
+    pub fee_epoch_y: Option<Box<Account<'info, FeeEpoch>>>,
+    /// Ranks maker_b by mint_x volume filled.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", mint_x.key().as_ref()],
+        bump = leaderboard_x.bump
+    )]
+    pub leaderboard_x: Option<Box<Account<'info, Leaderboard>>>,
+    /// Ranks maker_a by mint_y volume filled.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", mint_y.key().as_ref()],
+        bump = leaderboard_y.bump
+    )]
+    pub leaderboard_y: Option<Box<Account<'info, Leaderboard>>>,
+    /// Stats for escrow_a's pair, mint_x for mint_y.
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_x.key().as_ref(), mint_y.key().as_ref()],
+        bump = pair_stats_a.bump
+    )]
+    pub pair_stats_a: Option<Box<Account<'info, PairStats>>>,
+    /// Stats for escrow_b's pair, mint_y for mint_x.
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_y.key().as_ref(), mint_x.key().as_ref()],
+        bump = pair_stats_b.bump
+    )]
+    pub pair_stats_b: Option<Box<Account<'info, PairStats>>>,
+    #[account(
+        mut,
+        seeds = [b"keeper", matcher.key().as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Option<Account<'info, Keeper>>,
+    /// CHECK: the maker_a's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker_a.key().as_ref()],
+        bump,
+        constraint = maker_a_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_a_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker_b's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker_b.key().as_ref()],
+        bump,
+        constraint = maker_b_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_b_denylist_entry: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker_a.key().as_ref()],
+        bump = maker_a_directory.bump
+    )]
+    pub maker_a_directory: Option<Account<'info, MakerDirectory>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker_b.key().as_ref()],
+        bump = maker_b_directory.bump
+    )]
+    pub maker_b_directory: Option<Account<'info, MakerDirectory>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MatchOrders<'info> {
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_a_directory.as_mut() {
+            maker_directory.remove(&self.escrow_a.seed);
+        }
+        if let Some(maker_directory) = self.maker_b_directory.as_mut() {
+            maker_directory.remove(&self.escrow_b.seed);
+        }
+        Ok(())
+    }
+
+    /// The matcher is paid in tokens directly, so a match only counts towards the keeper's cranks.
+    pub fn record_crank(&mut self) -> Result<()> {
+        match self.keeper.as_mut() {
+            Some(keeper) => keeper.record_crank(0),
+            None => Ok(()),
+        }
+    }
+
+    pub fn check_crossing(&self) -> Result<()> {
+        let now = Clock::get()?.unix_timestamp;
+        // Each escrow is taken by the other's maker.
+        self.escrow_a
+            .check_takeable(&self.escrow_b.maker, now, &self.config)?;
+        self.escrow_b
+            .check_takeable(&self.escrow_a.maker, now, &self.config)?;
+        require!(
+            self.vault_a.amount >= self.escrow_b.receive
+                && self.vault_b.amount >= self.escrow_a.receive,
+            EscrowError::PricesDoNotCross
+        );
+        Ok(())
+    }
+
+    /// Take fee on filling `escrow`, charged on its ask like in `take`.
+    fn take_fee(
+        &self,
+        escrow: &Escrow,
+        fee_override: &Option<Box<Account<'info, FeeOverride>>>,
+        fee_stake: &Option<Box<Account<'info, FeeStake>>>,
+    ) -> Result<u64> {
+        self.config.take_fee(
+            escrow.receive,
+            fee_override.as_deref().map(|fee_override| &**fee_override),
+            fee_stake.as_deref().map(|fee_stake| &**fee_stake),
+        )
+    }
+
+    fn treasury(
+        treasury_ata: Option<&InterfaceAccount<'info, TokenAccount>>,
+        fee: u64,
+    ) -> Result<Option<AccountInfo<'info>>> {
+        if fee == 0 {
+            return Ok(None);
+        }
+        treasury_ata
+            .map(|treasury_ata| Some(treasury_ata.to_account_info()))
+            .ok_or(EscrowError::MissingFeeAccount.into())
+    }
+
+    /// Records each escrow as filled by the other's maker: escrow_a delivers the mint_x that
+    /// escrow_b asked for and is paid its own ask in mint_y, and escrow_b mirrors it.
+    pub fn record_fills(&mut self) -> Result<()> {
+        let fee_a = self.take_fee(&self.escrow_a, &self.fee_override_a, &self.fee_stake_b)?;
+        let fee_b = self.take_fee(&self.escrow_b, &self.fee_override_b, &self.fee_stake_a)?;
+
+        let fill_a = Fill {
+            maker: self.maker_a.key(),
+            taker: self.maker_b.key(),
+            amount: self.escrow_b.receive,
+            receive: self.escrow_a.receive,
+            treasury_fee: fee_a,
+            rebate: 0,
+        };
+        FillRecords {
+            pair_stats: self
+                .pair_stats_a
+                .as_deref_mut()
+                .map(|pair_stats| &mut **pair_stats),
+            fee_epoch: self
+                .fee_epoch_y
+                .as_deref_mut()
+                .map(|fee_epoch| &mut **fee_epoch),
+            leaderboard: self
+                .leaderboard_y
+                .as_deref_mut()
+                .map(|leaderboard| &mut **leaderboard),
+            ..Default::default()
+        }
+        .record(&fill_a)?;
+
+        let fill_b = Fill {
+            maker: self.maker_b.key(),
+            taker: self.maker_a.key(),
+            amount: self.escrow_a.receive,
+            receive: self.escrow_b.receive,
+            treasury_fee: fee_b,
+            rebate: 0,
+        };
+        FillRecords {
+            pair_stats: self
+                .pair_stats_b
+                .as_deref_mut()
+                .map(|pair_stats| &mut **pair_stats),
+            fee_epoch: self
+                .fee_epoch_x
+                .as_deref_mut()
+                .map(|fee_epoch| &mut **fee_epoch),
+            leaderboard: self
+                .leaderboard_x
+                .as_deref_mut()
+                .map(|leaderboard| &mut **leaderboard),
+            ..Default::default()
+        }
+        .record(&fill_b)
+    }
+
+    pub fn settle(&mut self) -> Result<()> {
+        let fee_a = self.take_fee(&self.escrow_a, &self.fee_override_a, &self.fee_stake_b)?;
+        let fee_b = self.take_fee(&self.escrow_b, &self.fee_override_b, &self.fee_stake_a)?;
+
+        // mint_x out of vault_a: maker_b's ask less the take fee, the fee, and the surplus back to
+        // maker_a less the matcher's cut of it.
+        let escrow_a = &self.escrow_a;
+        let seeds_a: &[&[u8]] = &[
+            b"escrow",
+            escrow_a.maker.as_ref(),
+            &escrow_a.seed[..],
+            &[escrow_a.bump],
+        ];
+        let surplus_x = self
+            .vault_a
+            .amount
+            .checked_sub(self.escrow_b.receive)
+            .ok_or(EscrowError::Overflow)?;
+        let incentive_x = bps_of(surplus_x, self.config.match_incentive_bps)?;
+        for (to, amount) in [
+            (
+                Some(self.maker_b_ata_x.to_account_info()),
+                self.escrow_b
+                    .receive
+                    .checked_sub(fee_b)
+                    .ok_or(EscrowError::Overflow)?,
+            ),
+            (
+                Self::treasury(self.treasury_ata_x.as_deref(), fee_b)?,
+                fee_b,
+            ),
+            (Some(self.matcher_ata_x.to_account_info()), incentive_x),
+            (
+                Some(self.maker_a_ata_x.to_account_info()),
+                surplus_x
+                    .checked_sub(incentive_x)
+                    .ok_or(EscrowError::Overflow)?,
+            ),
+        ] {
+            let Some(to) = to else {
+                continue;
+            };
+            self.transfer_from_vault(
+                self.vault_a.to_account_info(),
+                self.mint_x.as_ref(),
+                to,
+                escrow_a.to_account_info(),
+                seeds_a,
+                amount,
+            )?;
+        }
+
+        // mint_y out of vault_b, mirrored.
+        let escrow_b = &self.escrow_b;
+        let seeds_b: &[&[u8]] = &[
+            b"escrow",
+            escrow_b.maker.as_ref(),
+            &escrow_b.seed[..],
+            &[escrow_b.bump],
+        ];
+        let surplus_y = self
+            .vault_b
+            .amount
+            .checked_sub(self.escrow_a.receive)
+            .ok_or(EscrowError::Overflow)?;
+        let incentive_y = bps_of(surplus_y, self.config.match_incentive_bps)?;
+        for (to, amount) in [
+            (
+                Some(self.maker_a_ata_y.to_account_info()),
+                self.escrow_a
+                    .receive
+                    .checked_sub(fee_a)
+                    .ok_or(EscrowError::Overflow)?,
+            ),
+            (
+                Self::treasury(self.treasury_ata_y.as_deref(), fee_a)?,
+                fee_a,
+            ),
+            (Some(self.matcher_ata_y.to_account_info()), incentive_y),
+            (
+                Some(self.maker_b_ata_y.to_account_info()),
+                surplus_y
+                    .checked_sub(incentive_y)
+                    .ok_or(EscrowError::Overflow)?,
+            ),
+        ] {
+            let Some(to) = to else {
+                continue;
+            };
+            self.transfer_from_vault(
+                self.vault_b.to_account_info(),
+                self.mint_y.as_ref(),
+                to,
+                escrow_b.to_account_info(),
+                seeds_b,
+                amount,
+            )?;
+        }
+
+        emit!(MatchEvent {
+            escrow_a: self.escrow_a.key(),
+            escrow_b: self.escrow_b.key(),
+            matcher: self.matcher.key(),
+            fee_a,
+            fee_b,
+            incentive_x,
+            incentive_y,
+        });
+
+        for (vault, destination, authority, seeds) in [
+            (
+                self.vault_a.to_account_info(),
+                self.maker_a.to_account_info(),
+                self.escrow_a.to_account_info(),
+                seeds_a,
+            ),
+            (
+                self.vault_b.to_account_info(),
+                self.maker_b.to_account_info(),
+                self.escrow_b.to_account_info(),
+                seeds_b,
+            ),
+        ] {
+            let accounts = CloseAccount {
+                account: vault,
+                destination,
+                authority,
+            };
+
+            let signer_seeds = [seeds];
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                accounts,
+                &signer_seeds,
+            );
+
+            close_account(ctx)?;
+        }
+        Ok(())
+    }
+
+    fn transfer_from_vault(
+        &self,
+        vault: AccountInfo<'info>,
+        mint: &InterfaceAccount<'info, Mint>,
+        to: AccountInfo<'info>,
+        escrow: AccountInfo<'info>,
+        seeds: &[&[u8]],
+        amount: u64,
+    ) -> Result<()> {
+        if amount == 0 {
+            return Ok(());
+        }
+
+        let accounts = TransferChecked {
+            from: vault,
+            mint: mint.to_account_info(),
+            to,
+            authority: escrow,
+        };
+
+        let signer_seeds = [seeds];
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, amount, mint.decimals)
+    }
+}
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{
+    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+use crate::{Escrow, EscrowError, MakerDirectory};
+
+/// Folds `source` into `escrow` when both sell the same pair at the same price, closing the
+/// source escrow and its vault.
+#[derive(Accounts)]
+pub struct MergeEscrows<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = !escrow.delegated @ EscrowError::InvalidEscrowMode,
+        constraint = !escrow.is_streaming() @ EscrowError::AlreadyTaken,
+        constraint = escrow.same_price_as(&source) @ EscrowError::PriceMismatch,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        constraint = source.key() != escrow.key() @ EscrowError::InvalidSeed,
+        constraint = !source.delegated @ EscrowError::InvalidEscrowMode,
+        constraint = !source.is_streaming() @ EscrowError::AlreadyTaken,
+        seeds = [b"escrow", maker.key().as_ref(), source.seed.as_ref()],
+        bump = source.bump
+    )]
+    pub source: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = source,
+        associated_token::token_program = token_program,
+        constraint = source.holds_vault(&source_vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub source_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> MergeEscrows<'info> {
+    /// Removes the merged-away escrow from the directory.
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.source.seed);
+        }
+        Ok(())
+    }
+
+    pub fn merge(&mut self) -> Result<()> {
+        let source = &self.source;
+        let escrow = &mut self.escrow;
+
+        escrow.amount = escrow
+            .amount
+            .checked_add(source.amount)
+            .ok_or(EscrowError::Overflow)?;
+        escrow.receive = escrow
+            .receive
+            .checked_add(source.receive)
+            .ok_or(EscrowError::Overflow)?;
+        for (option, other) in escrow
+            .payment_options
+            .iter_mut()
+            .zip(source.payment_options.iter())
+        {
+            option.receive = option
+                .receive
+                .checked_add(other.receive)
+                .ok_or(EscrowError::Overflow)?;
+        }
+        escrow.record_update(Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+
+    pub fn move_and_close_vault(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.source.seed[..],
+            &[self.source.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.source_vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.source.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.source_vault.amount, self.mint_a.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.source_vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.source.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+}
@@ -0,0 +1,58 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::Config;
+
+/// Grows a config written by an older program version to the current layout, with the payer
+/// covering the extra rent. Appended bytes are zeroed, so new fields start at their defaults.
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: may still be in a legacy layout that doesn't deserialize; the discriminator is
+    /// checked in the handler before anything is written.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [b"config"],
+        bump
+    )]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateConfig<'info> {
+    pub fn migrate_config(&mut self) -> Result<()> {
+        let config = self.config.to_account_info();
+        require!(
+            config
+                .try_borrow_data()?
+                .starts_with(&Config::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+
+        let space = 8 + Config::INIT_SPACE;
+        if config.data_len() >= space {
+            return Ok(());
+        }
+
+        let rent = Rent::get()?
+            .minimum_balance(space)
+            .saturating_sub(config.lamports());
+        if rent > 0 {
+            let transfer_accounts = Transfer {
+                from: self.payer.to_account_info(),
+                to: config.clone(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+            transfer(cpi_ctx, rent)?;
+        }
+        config.realloc(space, true)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+    Discriminator,
+};
+
+use crate::{Escrow, EscrowError};
+
+/// Grows an escrow written by an older program version to the current layout, with the payer
+/// (normally the maker) covering the extra rent, and stamps it with the current `VERSION`. Only
+/// the layouts in `Escrow::MIGRATABLE_LENS` are accepted; first-release escrows and accounts of
+/// any other size are rejected untouched.
+#[derive(Accounts)]
+pub struct MigrateEscrow<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: may still be in a legacy layout that doesn't deserialize; the discriminator is
+    /// checked in the handler before anything is written.
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateEscrow<'info> {
+    pub fn migrate_escrow(&mut self) -> Result<()> {
+        let escrow = self.escrow.to_account_info();
+        require!(
+            escrow
+                .try_borrow_data()?
+                .starts_with(&Escrow::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        Escrow::check_migratable(escrow.data_len())?;
+
+        let space = 8 + Escrow::INIT_SPACE;
+        if escrow.data_len() < space {
+            let rent = Rent::get()?
+                .minimum_balance(space)
+                .saturating_sub(escrow.lamports());
+            if rent > 0 {
+                let transfer_accounts = Transfer {
+                    from: self.payer.to_account_info(),
+                    to: escrow.clone(),
+                };
+
+                let cpi_ctx =
+                    CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+                transfer(cpi_ctx, rent)?;
+            }
+            // Appended bytes read as defaults for fields newer than the account.
+            escrow.realloc(space, true)?;
+        }
+
+        let mut data = escrow.try_borrow_mut_data()?;
+        let mut state = Escrow::try_deserialize(&mut &data[..])?;
+        require_gte!(
+            Escrow::VERSION,
+            state.version,
+            EscrowError::UnsupportedVersion
+        );
+        state.version = Escrow::VERSION;
+        state.try_serialize(&mut &mut data[..])
+    }
+}
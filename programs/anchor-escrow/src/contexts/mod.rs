@@ -6,3 +6,267 @@ pub use refund::*;
 
 pub mod take;
 pub use take::*;
+
+pub mod init_trade_log;
+pub use init_trade_log::*;
+
+pub mod init_pair_stats;
+pub use init_pair_stats::*;
+
+pub mod init_fee_epoch;
+pub use init_fee_epoch::*;
+
+pub mod init_points;
+pub use init_points::*;
+
+pub mod set_points_rate;
+pub use set_points_rate::*;
+
+pub mod init_leaderboard;
+pub use init_leaderboard::*;
+
+pub mod init_maker_directory;
+pub use init_maker_directory::*;
+
+pub mod unlist_escrow;
+pub use unlist_escrow::*;
+
+pub mod init_maker_counter;
+pub use init_maker_counter::*;
+
+pub mod register_keeper;
+pub use register_keeper::*;
+
+pub mod claim_keeper_rewards;
+pub use claim_keeper_rewards::*;
+
+pub mod create_session;
+pub use create_session::*;
+
+pub mod revoke_session;
+pub use revoke_session::*;
+
+pub mod auto_refund;
+pub use auto_refund::*;
+
+pub mod claim_stream;
+pub use claim_stream::*;
+
+pub mod flash_take;
+pub use flash_take::*;
+
+pub mod flash_repay;
+pub use flash_repay::*;
+
+pub mod take_partial;
+pub use take_partial::*;
+
+pub mod take_with_route;
+pub use take_with_route::*;
+
+pub mod clone_escrow;
+pub use clone_escrow::*;
+
+pub mod create_template;
+pub use create_template::*;
+
+pub mod close_template;
+pub use close_template::*;
+
+pub mod make_from_template;
+pub use make_from_template::*;
+
+pub mod make_subscription;
+pub use make_subscription::*;
+
+pub mod claim_period;
+pub use claim_period::*;
+
+pub mod cancel_subscription;
+pub use cancel_subscription::*;
+
+pub mod split_escrow;
+pub use split_escrow::*;
+
+pub mod merge_escrows;
+pub use merge_escrows::*;
+
+pub mod update_escrow;
+pub use update_escrow::*;
+
+pub mod extend_escrow;
+pub use extend_escrow::*;
+
+pub mod make_delegated;
+pub use make_delegated::*;
+
+pub mod make_with_signature;
+pub use make_with_signature::*;
+
+pub mod cancel_delegated;
+pub use cancel_delegated::*;
+
+pub mod take_delegated;
+pub use take_delegated::*;
+
+pub mod make_stake;
+pub use make_stake::*;
+
+pub mod take_stake;
+pub use take_stake::*;
+
+pub mod refund_stake;
+pub use refund_stake::*;
+
+pub mod init_offer_tree;
+pub use init_offer_tree::*;
+
+pub mod make_compressed;
+pub use make_compressed::*;
+
+pub mod take_compressed;
+pub use take_compressed::*;
+
+pub mod refund_compressed;
+pub use refund_compressed::*;
+
+pub mod create_rfq;
+pub use create_rfq::*;
+
+pub mod cancel_rfq;
+pub use cancel_rfq::*;
+
+pub mod submit_quote;
+pub use submit_quote::*;
+
+pub mod accept_quote;
+pub use accept_quote::*;
+
+pub mod withdraw_quote;
+pub use withdraw_quote::*;
+
+pub mod match_orders;
+pub use match_orders::*;
+
+pub mod initialize_config;
+pub use initialize_config::*;
+
+pub mod update_config;
+pub use update_config::*;
+
+pub mod queue_config_change;
+pub use queue_config_change::*;
+
+pub mod execute_config_change;
+pub use execute_config_change::*;
+
+pub mod cancel_config_change;
+pub use cancel_config_change::*;
+
+pub mod nominate_admin;
+pub use nominate_admin::*;
+
+pub mod accept_admin;
+pub use accept_admin::*;
+
+pub mod migrate_escrow;
+pub use migrate_escrow::*;
+
+pub mod refund_legacy;
+pub use refund_legacy::*;
+
+pub mod migrate_config;
+pub use migrate_config::*;
+
+pub mod sweep;
+pub use sweep::*;
+
+pub mod recover_lamports;
+pub use recover_lamports::*;
+
+pub mod place_bid;
+pub use place_bid::*;
+
+pub mod accept_bid;
+pub use accept_bid::*;
+
+pub mod withdraw_bid;
+pub use withdraw_bid::*;
+
+pub mod quote_ui_amounts;
+pub use quote_ui_amounts::*;
+
+pub mod quote_with_fees;
+pub use quote_with_fees::*;
+
+pub mod freeze_escrow;
+pub use freeze_escrow::*;
+
+pub mod queue_emergency_withdraw;
+pub use queue_emergency_withdraw::*;
+
+pub mod cancel_emergency_withdraw;
+pub use cancel_emergency_withdraw::*;
+
+pub mod emergency_withdraw;
+pub use emergency_withdraw::*;
+
+pub mod set_fee_override;
+pub use set_fee_override::*;
+
+pub mod remove_fee_override;
+pub use remove_fee_override::*;
+
+pub mod register_router;
+pub use register_router::*;
+
+pub mod remove_router;
+pub use remove_router::*;
+
+pub mod set_buyback_config;
+pub use set_buyback_config::*;
+
+pub mod buyback_and_burn;
+pub use buyback_and_burn::*;
+
+pub mod deny_address;
+pub use deny_address::*;
+
+pub mod allow_address;
+pub use allow_address::*;
+
+pub mod set_protocol_fee_rate;
+pub use set_protocol_fee_rate::*;
+
+pub mod register_affiliate;
+pub use register_affiliate::*;
+
+pub mod claim_affiliate_fees;
+pub use claim_affiliate_fees::*;
+
+pub mod init_insurance_pool;
+pub use init_insurance_pool::*;
+
+pub mod file_insurance_claim;
+pub use file_insurance_claim::*;
+
+pub mod approve_insurance_claim;
+pub use approve_insurance_claim::*;
+
+pub mod reject_insurance_claim;
+pub use reject_insurance_claim::*;
+
+pub mod stake;
+pub use stake::*;
+
+pub mod unstake;
+pub use unstake::*;
+
+pub mod set_rebate_schedule;
+pub use set_rebate_schedule::*;
+
+pub mod init_taker_rebate;
+pub use init_taker_rebate::*;
+
+pub mod claim_rebate;
+pub use claim_rebate::*;
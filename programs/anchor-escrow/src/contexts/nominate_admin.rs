@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError};
+
+#[derive(Accounts)]
+pub struct NominateAdmin<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> NominateAdmin<'info> {
+    pub fn nominate_admin(&mut self, pending_admin: Pubkey) -> Result<()> {
+        self.config.pending_admin = pending_admin;
+        Ok(())
+    }
+}
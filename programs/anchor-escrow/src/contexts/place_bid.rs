@@ -0,0 +1,108 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{Bid, BidEvent, Config, Escrow, EscrowError};
+
+#[derive(Accounts)]
+pub struct PlaceBid<'info> {
+    #[account(mut)]
+    pub bidder: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_ata_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        has_one = mint_b,
+        constraint = escrow.sealed_bids @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", escrow.maker.as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = bidder,
+        space = 8 + Bid::INIT_SPACE,
+        seeds = [b"bid", escrow.key().as_ref(), bidder.key().as_ref()],
+        bump
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(
+        init,
+        payer = bidder,
+        associated_token::mint = mint_b,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: the bidder's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", bidder.key().as_ref()],
+        bump,
+        constraint = bidder_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub bidder_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> PlaceBid<'info> {
+    pub fn place_bid(
+        &mut self,
+        amount: u64,
+        locked_until: i64,
+        bumps: &PlaceBidBumps,
+    ) -> Result<()> {
+        self.escrow.check_open(
+            &self.bidder.key(),
+            Clock::get()?.unix_timestamp,
+            &self.config,
+        )?;
+
+        self.bid.set_inner(Bid {
+            escrow: self.escrow.key(),
+            bidder: self.bidder.key(),
+            amount,
+            locked_until,
+            bump: bumps.bid,
+        });
+
+        emit!(BidEvent {
+            escrow: self.escrow.key(),
+            bid: self.bid.key(),
+            bidder: self.bidder.key(),
+            amount,
+            accepted: false,
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self) -> Result<()> {
+        let transfer_accounts = TransferChecked {
+            from: self.bidder_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.bidder.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, self.bid.amount, self.mint_b.decimals)
+    }
+}
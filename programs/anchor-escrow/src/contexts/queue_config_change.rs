@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, ConfigParams, EscrowError, PendingConfigChange};
+
+#[derive(Accounts)]
+pub struct QueueConfigChange<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + PendingConfigChange::INIT_SPACE,
+        seeds = [b"pending_config_change"],
+        bump
+    )]
+    pub pending_config_change: Account<'info, PendingConfigChange>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QueueConfigChange<'info> {
+    pub fn queue_config_change(
+        &mut self,
+        params: ConfigParams,
+        bumps: &QueueConfigChangeBumps,
+    ) -> Result<()> {
+        params.validate()?;
+
+        let execute_after = Clock::get()?
+            .unix_timestamp
+            .checked_add(self.config.timelock)
+            .ok_or(EscrowError::Overflow)?;
+
+        self.pending_config_change.set_inner(PendingConfigChange {
+            params,
+            config_hash: self.config.params_hash()?,
+            execute_after,
+            bump: bumps.pending_config_change,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    Config, EmergencyWithdrawQueuedEvent, EmergencyWithdrawal, Escrow, EscrowError,
+    MIN_EMERGENCY_DELAY,
+};
+
+#[derive(Accounts)]
+pub struct QueueEmergencyWithdraw<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        constraint = !escrow.delegated @ EscrowError::InvalidEscrowMode,
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + EmergencyWithdrawal::INIT_SPACE,
+        seeds = [b"emergency_withdrawal", escrow.key().as_ref()],
+        bump
+    )]
+    pub emergency_withdrawal: Account<'info, EmergencyWithdrawal>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> QueueEmergencyWithdraw<'info> {
+    pub fn queue_emergency_withdraw(&mut self, bumps: &QueueEmergencyWithdrawBumps) -> Result<()> {
+        let execute_after = Clock::get()?
+            .unix_timestamp
+            .checked_add(self.config.timelock.max(MIN_EMERGENCY_DELAY))
+            .ok_or(EscrowError::Overflow)?;
+
+        self.emergency_withdrawal.set_inner(EmergencyWithdrawal {
+            escrow: self.escrow.key(),
+            execute_after,
+            bump: bumps.emergency_withdrawal,
+        });
+
+        emit!(EmergencyWithdrawQueuedEvent {
+            escrow: self.escrow.key(),
+            maker: self.escrow.maker,
+            execute_after,
+        });
+        Ok(())
+    }
+}
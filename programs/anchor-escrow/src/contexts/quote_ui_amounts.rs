@@ -0,0 +1,43 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{ui_amount, Escrow};
+
+/// UI-adjusted view of an escrow's raw amounts at the current timestamp.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct UiQuote {
+    pub deposit: String,
+    pub receive: String,
+}
+
+#[derive(Accounts)]
+pub struct QuoteUiAmounts<'info> {
+    #[account(
+        has_one = mint_a,
+        has_one = mint_b
+    )]
+    pub escrow: Account<'info, Escrow>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+}
+
+impl<'info> QuoteUiAmounts<'info> {
+    pub fn quote_ui_amounts(&self) -> Result<UiQuote> {
+        let now = Clock::get()?.unix_timestamp;
+        Ok(UiQuote {
+            deposit: ui_amount(
+                &self.mint_a.to_account_info(),
+                self.escrow.amount,
+                self.mint_a.decimals,
+                now,
+            )?,
+            receive: ui_amount(
+                &self.mint_b.to_account_info(),
+                self.escrow.receive,
+                self.mint_b.decimals,
+                now,
+            )?,
+        })
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{transfer_fee, Config, Escrow, EscrowError, FeeOverride, FeeStake, InsurancePool};
+
+/// Amounts that settle on both legs of a fill, in raw units. Gross amounts leave the sender and
+/// net amounts reach the recipient.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct FeeQuote {
+    /// mint_a leaving the vault.
+    pub gross_a: u64,
+    /// mint_a credited to the taker after the mint_a transfer fee.
+    pub net_a: u64,
+    /// mint_b debited from the taker.
+    pub gross_b: u64,
+    /// Take fee in mint_b sent to the treasury out of `gross_b`.
+    pub fee: u64,
+    /// Fee in mint_b sent to the escrow's frontend out of `gross_b`.
+    pub frontend_fee: u64,
+    /// Insurance premium in mint_b sent to the pool out of `gross_b`, on top of the price.
+    pub insurance_premium: u64,
+    /// mint_b credited to the maker after the take and frontend fees, payout splits, donation
+    /// and the mint_b transfer fee.
+    pub net_b: u64,
+}
+
+/// Quotes a fill the way `take` settles it, or `take_partial` for escrows that allow partial
+/// fills. A full take delivers the whole vault and may be priced in any of the escrow's payment
+/// mints. Fees paid in `config.protocol_mint` are not covered.
+#[derive(Accounts)]
+pub struct QuoteWithFees<'info> {
+    #[account(
+        has_one = mint_a,
+        constraint = escrow.receive_in(&mint_b.key()).is_ok() @ EscrowError::InvalidPaymentOption
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        token::mint = mint_a,
+        token::authority = escrow,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"fee_override", escrow.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+    /// The taker's staked protocol tokens, to quote the discounted take fee.
+    #[account(
+        seeds = [b"fee_stake", fee_stake.owner.as_ref()],
+        bump = fee_stake.bump
+    )]
+    pub fee_stake: Option<Account<'info, FeeStake>>,
+    /// To quote an insured take, which pays `config.insurance_premium_bps` on top.
+    #[account(
+        seeds = [b"insurance_pool", mint_b.key().as_ref()],
+        bump = insurance_pool.bump
+    )]
+    pub insurance_pool: Option<Account<'info, InsurancePool>>,
+}
+
+impl<'info> QuoteWithFees<'info> {
+    pub fn quote_with_fees(&self, fill: u64) -> Result<FeeQuote> {
+        let (fill, price, insurance_premium) = if self.escrow.allow_partial_fills {
+            require_keys_eq!(
+                self.mint_b.key(),
+                self.escrow.mint_b,
+                EscrowError::InvalidPaymentOption
+            );
+            let fill = self.escrow.sweep_dust(fill, self.config.dust_threshold);
+            (fill, self.escrow.fill_price(fill)?, 0)
+        } else {
+            require!(fill == self.escrow.amount, EscrowError::InvalidFillAmount);
+            let price = self.escrow.receive_in(&self.mint_b.key())?;
+            let insurance_premium = match self.insurance_pool {
+                Some(_) => self.config.insurance_premium(price)?,
+                None => 0,
+            };
+            (self.vault.amount, price, insurance_premium)
+        };
+
+        let fee = self.config.take_fee(
+            price,
+            self.fee_override.as_deref(),
+            self.fee_stake.as_deref(),
+        )?;
+        let frontend_fee = self.escrow.frontend_fee(price)?;
+        let after_fees = price
+            .checked_sub(fee.checked_add(frontend_fee).ok_or(EscrowError::Overflow)?)
+            .ok_or(EscrowError::Overflow)?;
+        let proceeds = self.escrow.maker_proceeds(after_fees)?;
+
+        let epoch = Clock::get()?.epoch;
+        Ok(FeeQuote {
+            gross_a: fill,
+            net_a: fill
+                .checked_sub(transfer_fee(&self.mint_a.to_account_info(), fill, epoch)?)
+                .ok_or(EscrowError::Overflow)?,
+            gross_b: price
+                .checked_add(insurance_premium)
+                .ok_or(EscrowError::Overflow)?,
+            fee,
+            frontend_fee,
+            insurance_premium,
+            net_b: proceeds
+                .checked_sub(transfer_fee(
+                    &self.mint_b.to_account_info(),
+                    proceeds,
+                    epoch,
+                )?)
+                .ok_or(EscrowError::Overflow)?,
+        })
+    }
+}
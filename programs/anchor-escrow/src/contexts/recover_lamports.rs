@@ -0,0 +1,81 @@
+use anchor_lang::{prelude::*, solana_program::program::invoke_signed};
+
+use anchor_spl::{
+    token_2022::{self, spl_token_2022},
+    token_interface::{TokenAccount, TokenInterface},
+};
+
+use crate::Escrow;
+
+/// Moves lamports above rent exemption from the escrow, and from a Token-2022 vault when one is
+/// supplied, to the maker. Legacy token vaults can't release excess lamports before they close.
+#[derive(Accounts)]
+pub struct RecoverLamports<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        mut,
+        associated_token::mint = escrow.mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Option<InterfaceAccount<'info, TokenAccount>>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RecoverLamports<'info> {
+    pub fn recover_escrow_lamports(&mut self) -> Result<()> {
+        let escrow = self.escrow.to_account_info();
+        let excess = escrow
+            .lamports()
+            .saturating_sub(Rent::get()?.minimum_balance(escrow.data_len()));
+
+        if excess > 0 {
+            escrow.sub_lamports(excess)?;
+            self.maker.add_lamports(excess)?;
+        }
+        Ok(())
+    }
+
+    pub fn recover_vault_lamports(&mut self) -> Result<()> {
+        let Some(vault) = self.vault.as_ref() else {
+            return Ok(());
+        };
+        if self.token_program.key() != token_2022::ID {
+            return Ok(());
+        }
+
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        let ix = spl_token_2022::instruction::withdraw_excess_lamports(
+            &self.token_program.key(),
+            &vault.key(),
+            &self.maker.key(),
+            &self.escrow.key(),
+            &[],
+        )?;
+
+        invoke_signed(
+            &ix,
+            &[
+                vault.to_account_info(),
+                self.maker.to_account_info(),
+                self.escrow.to_account_info(),
+            ],
+            &signer_seeds,
+        )?;
+        Ok(())
+    }
+}
@@ -1,20 +1,34 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
 
 use anchor_spl::{
     associated_token::AssociatedToken,
+    memo::{build_memo, BuildMemo, Memo},
     token_interface::{
         close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
         TransferChecked,
     },
 };
 
-use crate::Escrow;
+use crate::{bps_of, Config, Escrow, EscrowError, MakerDirectory, RefundEvent, VaultDrainedEvent};
 
+/// Signed by the maker or their delegate; the deposit always returns to the maker.
 #[derive(Accounts)]
 pub struct Refund<'info> {
     #[account(mut)]
-    maker: Signer<'info>,
+    authority: Signer<'info>,
+    #[account(mut)]
+    maker: SystemAccount<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
     mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    mint_b: InterfaceAccount<'info, Mint>,
     #[account(
         mut,
         associated_token::mint = mint_a,
@@ -26,8 +40,12 @@ pub struct Refund<'info> {
         mut,
         close = maker,
         has_one = mint_a,
+        has_one = mint_b,
         has_one = maker,
-        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        constraint = escrow.is_manager(&authority.key()) @ EscrowError::Unauthorized,
+        constraint = !escrow.delegated @ EscrowError::InvalidEscrowMode,
+        constraint = !escrow.is_streaming() @ EscrowError::AlreadyTaken,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
         bump = escrow.bump
     )]
     escrow: Account<'info, Escrow>,
@@ -35,37 +53,162 @@ pub struct Refund<'info> {
         mut,
         associated_token::mint = mint_a,
         associated_token::authority = escrow,
-        associated_token::token_program = token_program
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    config: Account<'info, Config>,
+    #[account(
+        mut,
+        address = config.treasury
+    )]
+    treasury: SystemAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program
+    )]
+    treasury_ata_a: Option<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    maker_directory: Option<Account<'info, MakerDirectory>>,
+    memo_program: Option<Program<'info, Memo>>,
     associated_token_program: Program<'info, AssociatedToken>,
     token_program: Interface<'info, TokenInterface>,
     system_program: Program<'info, System>,
 }
 
 impl<'info> Refund<'info> {
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+        }
+        Ok(())
+    }
+
+    pub fn memo(&self) -> Result<()> {
+        let Some(memo_program) = self.memo_program.as_ref() else {
+            return Ok(());
+        };
+
+        let memo = format!("escrow:{} refund:{}", self.escrow.key(), self.vault.amount);
+
+        build_memo(
+            CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+            memo.as_bytes(),
+        )
+    }
+
+    fn is_expired(&self) -> Result<bool> {
+        Ok(self
+            .escrow
+            .is_expired(Clock::get()?.unix_timestamp, self.config.max_duration))
+    }
+
+    pub fn check_unlocked(&self) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp >= self.escrow.locked_until,
+            EscrowError::RefundLocked
+        );
+        Ok(())
+    }
+
+    pub fn pay_cancellation_fee(&mut self) -> Result<()> {
+        if self.config.cancellation_fee == 0 || self.is_expired()? {
+            return Ok(());
+        }
+
+        let transfer_accounts = Transfer {
+            from: self.authority.to_account_info(),
+            to: self.treasury.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, self.config.cancellation_fee)
+    }
+
     pub fn refund_and_close_vault(&mut self) -> Result<()> {
         let signer_seeds: [&[&[u8]]; 1] = [&[
             b"escrow",
             self.maker.to_account_info().key.as_ref(),
-            &self.escrow.seed.to_le_bytes()[..],
+            &self.escrow.seed[..],
             &[self.escrow.bump],
         ]];
 
-        let xfer_accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            mint: self.mint_a.to_account_info(),
-            to: self.maker_ata_a.to_account_info(),
-            authority: self.escrow.to_account_info(),
+        let fee = if self.is_expired()? {
+            0
+        } else {
+            bps_of(self.vault.amount, self.config.cancellation_fee_bps)?
         };
 
-        let ctx = CpiContext::new_with_signer(
-            self.token_program.to_account_info(),
-            xfer_accounts,
-            &signer_seeds,
-        );
+        if fee > 0 {
+            let treasury_ata_a = self
+                .treasury_ata_a
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let xfer_accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: treasury_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                xfer_accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(ctx, fee, self.mint_a.decimals)?;
+        }
+
+        let amount = self
+            .vault
+            .amount
+            .checked_sub(fee)
+            .ok_or(EscrowError::Overflow)?;
+
+        if amount > 0 {
+            let xfer_accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.maker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                xfer_accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(ctx, amount, self.mint_a.decimals)?;
+        }
+
+        if self.vault.amount == 0 {
+            emit!(VaultDrainedEvent {
+                escrow: self.escrow.key(),
+                maker: self.maker.key(),
+                expected: self.escrow.amount,
+            });
+        }
 
-        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+        emit!(RefundEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            amount,
+            fee,
+        });
 
         let close_accounts = CloseAccount {
             account: self.vault.to_account_info(),
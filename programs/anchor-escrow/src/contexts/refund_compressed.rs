@@ -0,0 +1,83 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{CompressedOffer, CompressedSettleEvent, EscrowError, OfferTree};
+
+#[derive(Accounts)]
+#[instruction(index: u32, offer: CompressedOffer)]
+pub struct RefundCompressed<'info> {
+    #[account(
+        mut,
+        address = offer.maker @ EscrowError::Unauthorized
+    )]
+    pub maker: Signer<'info>,
+    #[account(
+        address = offer.mint_a
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut)]
+    pub offer_tree: AccountLoader<'info, OfferTree>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = offer_tree,
+        associated_token::token_program = token_program
+    )]
+    pub tree_vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RefundCompressed<'info> {
+    pub fn settle(
+        &mut self,
+        index: u32,
+        offer: &CompressedOffer,
+        proof: &[[u8; 32]],
+    ) -> Result<()> {
+        self.offer_tree.load_mut()?.settle(index, offer, proof)?;
+
+        emit!(CompressedSettleEvent {
+            offer_tree: self.offer_tree.key(),
+            index,
+            taker: None,
+        });
+        Ok(())
+    }
+
+    pub fn refund(&mut self, offer: &CompressedOffer) -> Result<()> {
+        let (id, bump) = {
+            let offer_tree = self.offer_tree.load()?;
+            (offer_tree.id, offer_tree.bump)
+        };
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"offer_tree", &id.to_le_bytes(), &[bump]]];
+
+        let accounts = TransferChecked {
+            from: self.tree_vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.maker_ata_a.to_account_info(),
+            authority: self.offer_tree.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, offer.amount, self.mint_a.decimals)
+    }
+}
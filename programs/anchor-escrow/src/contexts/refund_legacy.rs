@@ -0,0 +1,143 @@
+use anchor_lang::{prelude::*, Discriminator};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{Escrow, EscrowError, LegacyEscrow, RefundEvent};
+
+/// Returns the deposit of a first-release escrow to its maker and closes it. Those escrows
+/// derive from an 8-byte seed and can't be migrated to the current layout, so this is the only
+/// way their vaults are released.
+#[derive(Accounts)]
+pub struct RefundLegacy<'info> {
+    #[account(mut)]
+    maker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: in the first-release layout, which doesn't deserialize as `Escrow`; its size,
+    /// discriminator, maker, mint and address are checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID
+    )]
+    escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    vault: InterfaceAccount<'info, TokenAccount>,
+    associated_token_program: Program<'info, AssociatedToken>,
+    token_program: Interface<'info, TokenInterface>,
+    system_program: Program<'info, System>,
+}
+
+impl<'info> RefundLegacy<'info> {
+    fn load_escrow(&self) -> Result<LegacyEscrow> {
+        let data = self.escrow.try_borrow_data()?;
+        require!(
+            data.starts_with(&Escrow::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        require_eq!(
+            data.len(),
+            Escrow::BASELINE_LEN,
+            EscrowError::UnsupportedVersion
+        );
+        let escrow = LegacyEscrow::deserialize(&mut &data[8..])?;
+
+        require_keys_eq!(escrow.maker, self.maker.key(), ErrorCode::ConstraintHasOne);
+        require_keys_eq!(
+            escrow.mint_a,
+            self.mint_a.key(),
+            ErrorCode::ConstraintHasOne
+        );
+        let address = Pubkey::create_program_address(
+            &[
+                b"escrow",
+                escrow.maker.as_ref(),
+                &escrow.seed.to_le_bytes(),
+                &[escrow.bump],
+            ],
+            &crate::ID,
+        )
+        .map_err(|_| ErrorCode::ConstraintSeeds)?;
+        require_keys_eq!(address, self.escrow.key(), ErrorCode::ConstraintSeeds);
+
+        Ok(escrow)
+    }
+
+    pub fn refund_and_close(&mut self) -> Result<()> {
+        let escrow = self.load_escrow()?;
+        let seed = escrow.seed.to_le_bytes();
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &seed,
+            &[escrow.bump],
+        ]];
+
+        let amount = self.vault.amount;
+        if amount > 0 {
+            let xfer_accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.maker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                xfer_accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(ctx, amount, self.mint_a.decimals)?;
+        }
+
+        emit!(RefundEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            amount,
+            fee: 0,
+        });
+
+        let close_accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            close_accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        // Closed the way Anchor's `close` constraint would: rent to the maker, handed back to the
+        // system program with no data.
+        let escrow = self.escrow.to_account_info();
+        self.maker.add_lamports(escrow.lamports())?;
+        escrow.sub_lamports(escrow.lamports())?;
+        escrow.assign(&anchor_lang::system_program::ID);
+        escrow.realloc(0, false)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,65 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke_signed,
+        stake::{self, state::StakeAuthorize},
+    },
+};
+
+use crate::StakeEscrow;
+
+#[derive(Accounts)]
+pub struct RefundStake<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        seeds = [b"stake_escrow", maker.key().as_ref(), stake_escrow.seed.as_ref()],
+        bump = stake_escrow.bump
+    )]
+    pub stake_escrow: Account<'info, StakeEscrow>,
+    /// CHECK: address is bound to the escrow.
+    #[account(
+        mut,
+        address = stake_escrow.stake
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: the stake program.
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+impl<'info> RefundStake<'info> {
+    pub fn return_authorities(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"stake_escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.stake_escrow.seed[..],
+            &[self.stake_escrow.bump],
+        ]];
+
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            let ix = stake::instruction::authorize(
+                self.stake_account.key,
+                &self.stake_escrow.key(),
+                &self.maker.key(),
+                stake_authorize,
+                None,
+            );
+
+            invoke_signed(
+                &ix,
+                &[
+                    self.stake_account.to_account_info(),
+                    self.clock.to_account_info(),
+                    self.stake_escrow.to_account_info(),
+                ],
+                &signer_seeds,
+            )?;
+        }
+        Ok(())
+    }
+}
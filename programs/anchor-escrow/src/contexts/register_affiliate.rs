@@ -0,0 +1,50 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+use crate::Affiliate;
+
+#[derive(Accounts)]
+pub struct RegisterAffiliate<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = owner,
+        space = 8 + Affiliate::INIT_SPACE,
+        seeds = [b"affiliate", owner.key().as_ref(), mint.key().as_ref()],
+        bump
+    )]
+    pub affiliate: Account<'info, Affiliate>,
+    #[account(
+        init,
+        payer = owner,
+        associated_token::mint = mint,
+        associated_token::authority = affiliate,
+        associated_token::token_program = token_program
+    )]
+    pub affiliate_vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterAffiliate<'info> {
+    pub fn register_affiliate(&mut self, bumps: &RegisterAffiliateBumps) -> Result<()> {
+        self.affiliate.set_inner(Affiliate {
+            owner: self.owner.key(),
+            mint: self.mint.key(),
+            accrued: 0,
+            referrals: 0,
+            bump: bumps.affiliate,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+use anchor_lang::prelude::*;
+
+use crate::Keeper;
+
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Keeper::INIT_SPACE,
+        seeds = [b"keeper", authority.key().as_ref()],
+        bump
+    )]
+    pub keeper: Account<'info, Keeper>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterKeeper<'info> {
+    pub fn register_keeper(&mut self, bumps: &RegisterKeeperBumps) -> Result<()> {
+        self.keeper.set_inner(Keeper {
+            authority: self.authority.key(),
+            cranks: 0,
+            rewards: 0,
+            bump: bumps.keeper,
+        });
+        Ok(())
+    }
+}
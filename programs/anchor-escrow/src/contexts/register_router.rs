@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError, SwapRouter};
+
+#[derive(Accounts)]
+pub struct RegisterRouter<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    /// CHECK: swap program being whitelisted.
+    #[account(executable)]
+    pub program: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + SwapRouter::INIT_SPACE,
+        seeds = [b"swap_router", program.key().as_ref()],
+        bump
+    )]
+    pub swap_router: Account<'info, SwapRouter>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> RegisterRouter<'info> {
+    pub fn register_router(&mut self, bumps: &RegisterRouterBumps) -> Result<()> {
+        self.swap_router.set_inner(SwapRouter {
+            program: self.program.key(),
+            bump: bumps.swap_router,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError, InsuranceClaim};
+
+/// Closes an insurance claim without paying it, returning its rent to the claimant.
+#[derive(Accounts)]
+pub struct RejectInsuranceClaim<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(mut)]
+    pub claimant: SystemAccount<'info>,
+    #[account(
+        mut,
+        close = claimant,
+        has_one = claimant,
+        seeds = [b"insurance_claim", insurance_claim.escrow.as_ref(), claimant.key().as_ref()],
+        bump = insurance_claim.bump
+    )]
+    pub insurance_claim: Account<'info, InsuranceClaim>,
+}
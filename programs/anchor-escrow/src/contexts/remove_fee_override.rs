@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError, FeeOverride};
+
+#[derive(Accounts)]
+pub struct RemoveFeeOverride<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"fee_override", fee_override.escrow.as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+}
@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, EscrowError, SwapRouter};
+
+#[derive(Accounts)]
+pub struct RemoveRouter<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [b"swap_router", swap_router.program.as_ref()],
+        bump = swap_router.bump
+    )]
+    pub swap_router: Account<'info, SwapRouter>,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::{revoke, Revoke, TokenAccount, TokenInterface};
+
+use crate::SessionToken;
+
+/// Closes a session token and clears the token account's delegate.
+#[derive(Accounts)]
+pub struct RevokeSession<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        token::authority = authority,
+        token::token_program = token_program
+    )]
+    pub token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        close = authority,
+        has_one = authority,
+        seeds = [b"session_token", authority.key().as_ref(), session_token.signer.as_ref()],
+        bump = session_token.bump
+    )]
+    pub session_token: Account<'info, SessionToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> RevokeSession<'info> {
+    pub fn revoke_session(&mut self) -> Result<()> {
+        let accounts = Revoke {
+            source: self.token_account.to_account_info(),
+            authority: self.authority.to_account_info(),
+        };
+
+        revoke(CpiContext::new(
+            self.token_program.to_account_info(),
+            accounts,
+        ))
+    }
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{BuybackConfig, Config, EscrowError};
+
+#[derive(Accounts)]
+pub struct SetBuybackConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    /// Token bought back and burned.
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + BuybackConfig::INIT_SPACE,
+        seeds = [b"buyback_config"],
+        bump
+    )]
+    pub buyback_config: Account<'info, BuybackConfig>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetBuybackConfig<'info> {
+    pub fn set_buyback_config(
+        &mut self,
+        max_amount_in: u64,
+        bumps: &SetBuybackConfigBumps,
+    ) -> Result<()> {
+        self.buyback_config.set_inner(BuybackConfig {
+            mint: self.mint.key(),
+            max_amount_in,
+            bump: bumps.buyback_config,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, Escrow, EscrowError, FeeOverride};
+
+#[derive(Accounts)]
+pub struct SetFeeOverride<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + FeeOverride::INIT_SPACE,
+        seeds = [b"fee_override", escrow.key().as_ref()],
+        bump
+    )]
+    pub fee_override: Account<'info, FeeOverride>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetFeeOverride<'info> {
+    pub fn set_fee_override(&mut self, fee_bps: u16, bumps: &SetFeeOverrideBumps) -> Result<()> {
+        require!(fee_bps <= 10_000, EscrowError::InvalidBps);
+
+        self.fee_override.set_inner(FeeOverride {
+            escrow: self.escrow.key(),
+            fee_bps,
+            bump: bumps.fee_override,
+        });
+        Ok(())
+    }
+}
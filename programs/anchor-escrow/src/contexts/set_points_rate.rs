@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{Config, EscrowError, PointsRate};
+
+#[derive(Accounts)]
+pub struct SetPointsRate<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PointsRate::INIT_SPACE,
+        seeds = [b"points_rate", mint.key().as_ref()],
+        bump
+    )]
+    pub points_rate: Account<'info, PointsRate>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetPointsRate<'info> {
+    pub fn set_points_rate(
+        &mut self,
+        make_multiplier_bps: u64,
+        take_multiplier_bps: u64,
+        bumps: &SetPointsRateBumps,
+    ) -> Result<()> {
+        self.points_rate.set_inner(PointsRate {
+            mint: self.mint.key(),
+            make_multiplier_bps,
+            take_multiplier_bps,
+            bump: bumps.points_rate,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{Config, EscrowError, ProtocolFeeRate};
+
+#[derive(Accounts)]
+pub struct SetProtocolFeeRate<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ProtocolFeeRate::INIT_SPACE,
+        seeds = [b"protocol_fee_rate", mint.key().as_ref()],
+        bump
+    )]
+    pub protocol_fee_rate: Account<'info, ProtocolFeeRate>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetProtocolFeeRate<'info> {
+    pub fn set_protocol_fee_rate(
+        &mut self,
+        numerator: u64,
+        denominator: u64,
+        bumps: &SetProtocolFeeRateBumps,
+    ) -> Result<()> {
+        require!(denominator > 0, EscrowError::InvalidFeeRate);
+
+        self.protocol_fee_rate.set_inner(ProtocolFeeRate {
+            mint: self.mint.key(),
+            numerator,
+            denominator,
+            bump: bumps.protocol_fee_rate,
+        });
+        Ok(())
+    }
+}
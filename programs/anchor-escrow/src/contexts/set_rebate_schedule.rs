@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{Config, EscrowError, RebateSchedule, RebateTier, MAX_REBATE_TIERS};
+
+#[derive(Accounts)]
+pub struct SetRebateSchedule<'info> {
+    pub admin: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + RebateSchedule::INIT_SPACE,
+        seeds = [b"rebate_schedule", mint.key().as_ref()],
+        bump
+    )]
+    pub rebate_schedule: Account<'info, RebateSchedule>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SetRebateSchedule<'info> {
+    pub fn set_rebate_schedule(
+        &mut self,
+        tiers: [RebateTier; MAX_REBATE_TIERS],
+        bumps: &SetRebateScheduleBumps,
+    ) -> Result<()> {
+        for tier in &tiers {
+            require!(tier.rebate_bps <= 10_000, EscrowError::InvalidBps);
+        }
+
+        self.rebate_schedule.set_inner(RebateSchedule {
+            mint: self.mint.key(),
+            tiers,
+            bump: bumps.rebate_schedule,
+        });
+        Ok(())
+    }
+}
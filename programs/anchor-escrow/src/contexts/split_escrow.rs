@@ -0,0 +1,153 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{Escrow, EscrowError, MakeEvent, MakerDirectory};
+
+/// Moves `amount` of an escrow's deposit into a new child escrow under `seed` with the same terms
+/// and unit price. Repeat to offer a block at several sizes at once.
+#[derive(Accounts)]
+#[instruction(seed: [u8; 32])]
+pub struct SplitEscrow<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a,
+        constraint = !parent.delegated @ EscrowError::InvalidEscrowMode,
+        constraint = !parent.is_streaming() @ EscrowError::AlreadyTaken,
+        seeds = [b"escrow", maker.key().as_ref(), parent.seed.as_ref()],
+        bump = parent.bump
+    )]
+    pub parent: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = parent,
+        associated_token::token_program = token_program
+    )]
+    pub parent_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init,
+        payer = maker,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), seed.as_ref()],
+        bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        init,
+        payer = maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SplitEscrow<'info> {
+    pub fn split(&mut self, seed: [u8; 32], amount: u64, bumps: &SplitEscrowBumps) -> Result<()> {
+        require!(
+            amount > 0 && amount < self.parent.amount,
+            EscrowError::InvalidFillAmount
+        );
+
+        let mut child = (**self.parent).clone();
+        child.seed = seed;
+        child.bump = bumps.escrow;
+        child.version = Escrow::VERSION;
+        child.vault = self.vault.key();
+        child.amount = amount;
+        child.receive = self.parent.base_price(amount)?;
+        for option in child.payment_options.iter_mut() {
+            option.receive = self.parent.scale_price(option.receive, amount)?;
+        }
+        for (parent_option, option) in self
+            .parent
+            .payment_options
+            .iter_mut()
+            .zip(child.payment_options.iter())
+        {
+            parent_option.receive = parent_option
+                .receive
+                .checked_sub(option.receive)
+                .ok_or(EscrowError::Overflow)?;
+        }
+
+        self.parent.amount = self
+            .parent
+            .amount
+            .checked_sub(amount)
+            .ok_or(EscrowError::Overflow)?;
+        self.parent.receive = self
+            .parent
+            .receive
+            .checked_sub(child.receive)
+            .ok_or(EscrowError::Overflow)?;
+
+        emit!(MakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            mint_a: child.mint_a,
+            mint_b: child.mint_b,
+            deposit: child.amount,
+            receive: child.receive,
+            note: child.note,
+        });
+
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.insert(seed)?;
+        }
+
+        self.escrow.set_inner(child);
+        Ok(())
+    }
+
+    pub fn move_deposit(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.parent.seed[..],
+            &[self.parent.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.parent_vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.parent.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.escrow.amount, self.mint_a.decimals)
+    }
+}
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{Config, EscrowError, FeeStake};
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        address = config.protocol_mint,
+        mint::token_program = token_program
+    )]
+    pub protocol_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = protocol_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + FeeStake::INIT_SPACE,
+        seeds = [b"fee_stake", owner.key().as_ref()],
+        bump
+    )]
+    pub fee_stake: Account<'info, FeeStake>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = protocol_mint,
+        associated_token::authority = fee_stake,
+        associated_token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Stake<'info> {
+    pub fn stake(&mut self, amount: u64, bumps: &StakeBumps) -> Result<()> {
+        require!(amount > 0, EscrowError::InvalidStakeAmount);
+
+        let transfer_accounts = TransferChecked {
+            from: self.owner_ata.to_account_info(),
+            mint: self.protocol_mint.to_account_info(),
+            to: self.stake_vault.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, amount, self.protocol_mint.decimals)?;
+
+        self.fee_stake.owner = self.owner.key();
+        self.fee_stake.amount = self
+            .fee_stake
+            .amount
+            .checked_add(amount)
+            .ok_or(EscrowError::Overflow)?;
+        self.fee_stake.staked_slot = Clock::get()?.slot;
+        self.fee_stake.bump = bumps.fee_stake;
+        Ok(())
+    }
+}
@@ -0,0 +1,122 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{transfer, Transfer},
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{EscrowError, Quote, QuoteEvent, Rfq};
+
+#[derive(Accounts)]
+pub struct SubmitQuote<'info> {
+    #[account(mut)]
+    pub market_maker: Signer<'info>,
+    #[account(
+        mint::token_program = token_program
+    )]
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = market_maker,
+        associated_token::token_program = token_program
+    )]
+    pub market_maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = mint_a,
+        constraint = !rfq.closed @ EscrowError::RfqClosed,
+        seeds = [b"rfq", rfq.requester.as_ref(), rfq.seed.as_ref()],
+        bump = rfq.bump
+    )]
+    pub rfq: Account<'info, Rfq>,
+    #[account(
+        init,
+        payer = market_maker,
+        space = 8 + Quote::INIT_SPACE,
+        seeds = [b"quote", rfq.key().as_ref(), market_maker.key().as_ref()],
+        bump
+    )]
+    pub quote: Account<'info, Quote>,
+    #[account(
+        init,
+        payer = market_maker,
+        associated_token::mint = mint_a,
+        associated_token::authority = quote,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: the market maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", market_maker.key().as_ref()],
+        bump,
+        constraint = market_maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub market_maker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> SubmitQuote<'info> {
+    pub fn submit_quote(&mut self, receive: u64, bumps: &SubmitQuoteBumps) -> Result<()> {
+        require!(
+            Clock::get()?.unix_timestamp < self.rfq.expires_at,
+            EscrowError::RfqExpired
+        );
+
+        self.rfq.open_quotes = self
+            .rfq
+            .open_quotes
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+
+        self.quote.set_inner(Quote {
+            rfq: self.rfq.key(),
+            market_maker: self.market_maker.key(),
+            receive,
+            bond: self.rfq.bond,
+            bump: bumps.quote,
+        });
+
+        emit!(QuoteEvent {
+            rfq: self.rfq.key(),
+            quote: self.quote.key(),
+            market_maker: self.market_maker.key(),
+            receive,
+            accepted: false,
+        });
+        Ok(())
+    }
+
+    pub fn lock_bond(&mut self) -> Result<()> {
+        if self.rfq.bond == 0 {
+            return Ok(());
+        }
+
+        let transfer_accounts = Transfer {
+            from: self.market_maker.to_account_info(),
+            to: self.quote.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, self.rfq.bond)
+    }
+
+    pub fn deposit(&mut self) -> Result<()> {
+        let transfer_accounts = TransferChecked {
+            from: self.market_maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.vault.to_account_info(),
+            authority: self.market_maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, self.rfq.amount, self.mint_a.decimals)
+    }
+}
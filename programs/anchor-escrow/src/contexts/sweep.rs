@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{Escrow, EscrowError};
+
+/// Returns tokens of any mint other than mint_a that ended up in an escrow-owned token account,
+/// then closes that account.
+#[derive(Accounts)]
+pub struct Sweep<'info> {
+    #[account(mut)]
+    pub maker: Signer<'info>,
+    #[account(
+        has_one = maker,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        constraint = mint.key() != escrow.mint_a @ EscrowError::CannotSweepEscrowMint,
+        mint::token_program = token_program
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = escrow,
+        token::token_program = token_program
+    )]
+    pub stray_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = maker,
+        associated_token::mint = mint,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program
+    )]
+    pub maker_ata: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Sweep<'info> {
+    pub fn sweep(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        if self.stray_account.amount > 0 {
+            let accounts = TransferChecked {
+                from: self.stray_account.to_account_info(),
+                mint: self.mint.to_account_info(),
+                to: self.maker_ata.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(ctx, self.stray_account.amount, self.mint.decimals)?;
+        }
+
+        let accounts = CloseAccount {
+            account: self.stray_account.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+}
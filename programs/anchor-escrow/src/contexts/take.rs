@@ -1,26 +1,74 @@
-use anchor_lang::prelude::*;
+use anchor_lang::{
+    prelude::*,
+    solana_program::sysvar::instructions as instructions_sysvar,
+    system_program::{transfer, Transfer},
+};
 
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token_interface::{close_account, transfer_checked, Mint, TokenAccount, TokenInterface, CloseAccount, TransferChecked},
+    memo::{build_memo, BuildMemo, Memo},
+    token_interface::{
+        burn, close_account, sync_native, transfer_checked, Burn, CloseAccount, Mint, SyncNative,
+        TokenAccount, TokenInterface, TransferChecked,
+    },
 };
 
-use crate::Escrow;
+use crate::{
+    accepts_public_credits, bps_of, is_native_mint, Affiliate, Config, CredentialGate, Escrow,
+    EscrowError, FeeEpoch, FeeOverride, FeeStake, Fill, FillRecords, InsurancePool, Leaderboard,
+    MakeEvent, MakerDirectory, PairStats, PaymentOption, PayoutSplit, Points, PointsRate,
+    PriceTier, ProtocolFeeRate, RebateSchedule, SessionToken, SettlementMessage, TakeCondition,
+    TakeEvent, TakerRebate, TradeLog, VaultDrainedEvent, WormholeAccounts, MAX_ALLOWED_TAKERS,
+    MAX_PAYMENT_OPTIONS, MAX_PAYOUT_SPLITS, MAX_PRICE_TIERS,
+};
 
 #[derive(Accounts)]
+#[instruction(payment_index: u8)]
 pub struct Take<'info> {
+    /// Signs directly, or through `session_signer` when it holds a `session_token`.
+    #[account(mut)]
+    pub taker: SystemAccount<'info>,
     #[account(mut)]
-    pub taker: Signer<'info>,
+    pub payer: Signer<'info>,
+    pub session_signer: Option<Signer<'info>>,
+    #[account(
+        seeds = [b"session_token", taker.key().as_ref(), session_token.signer.as_ref()],
+        bump = session_token.bump
+    )]
+    pub session_token: Option<Box<Account<'info, SessionToken>>>,
+    /// Required when the escrow names a co-signer.
+    pub co_signer: Option<Signer<'info>>,
+    /// Required when the escrow requires compliance approval.
+    pub compliance_signer: Option<Signer<'info>>,
+    /// CHECK: taker's credential, required when the escrow has a credential gate and checked
+    /// against it.
+    pub credential: Option<UncheckedAccount<'info>>,
     #[account(mut)]
     pub maker: SystemAccount<'info>,
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
     pub mint_a: InterfaceAccount<'info, Mint>,
     pub mint_b: InterfaceAccount<'info, Mint>,
     #[account(
         init_if_needed,
-        payer = taker,
+        payer = payer,
         associated_token::mint = mint_a,
         associated_token::authority = taker,
         associated_token::token_program = token_program,
+        constraint = !taker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&taker_ata_a.to_account_info()) @ EscrowError::PublicCreditsDisabled,
     )]
     pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
@@ -29,22 +77,36 @@ pub struct Take<'info> {
         associated_token::authority = taker,
         associated_token::token_program = token_program,
     )]
-    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    pub taker_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Temporary wSOL account funded from the taker's lamports, used in place of `taker_ata_b`
+    /// when paying a wSOL mint_b in native SOL. Closed back to the payer after the payment.
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"taker_wsol", escrow.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = taker,
+        token::token_program = token_program,
+    )]
+    pub taker_wsol: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     #[account(
         init_if_needed,
-        payer = taker,
+        payer = payer,
         associated_token::mint = mint_b,
         associated_token::authority = maker,
         associated_token::token_program = token_program,
+        constraint = !maker_ata_b.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&maker_ata_b.to_account_info()) @ EscrowError::PublicCreditsDisabled,
     )]
     pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
     #[account(
         mut,
-        close = maker,
         has_one = maker,
         has_one = mint_a,
-        has_one = mint_b,
-        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.to_le_bytes().as_ref()],
+        constraint = escrow.payment_option(payment_index).map(|option| option.mint) == Some(mint_b.key()) @ EscrowError::InvalidPaymentOption,
+        constraint = !escrow.delegated && !escrow.is_scheduled() @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
         bump = escrow.bump
     )]
     escrow: Account<'info, Escrow>,
@@ -53,39 +115,788 @@ pub struct Take<'info> {
         associated_token::mint = mint_a,
         associated_token::authority = escrow,
         associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
     )]
     pub vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"fee_override", escrow.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = config.treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Receives `config.referral_share_bps` of the mint_b fee on behalf of its owner.
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::token_program = token_program,
+    )]
+    pub referrer_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Registered referrer; takes precedence over `referrer_ata_b` and accrues into `affiliate_vault`.
+    #[account(
+        mut,
+        seeds = [b"affiliate", affiliate.owner.as_ref(), mint_b.key().as_ref()],
+        bump = affiliate.bump
+    )]
+    pub affiliate: Option<Account<'info, Affiliate>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = affiliate,
+        associated_token::token_program = token_program,
+    )]
+    pub affiliate_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// When present, the protocol fee is paid in `config.protocol_mint` at the discounted rate.
+    #[account(
+        seeds = [b"protocol_fee_rate", mint_b.key().as_ref()],
+        bump = protocol_fee_rate.bump
+    )]
+    pub protocol_fee_rate: Option<Account<'info, ProtocolFeeRate>>,
+    #[account(
+        mut,
+        address = config.protocol_mint
+    )]
+    pub protocol_mint: Option<Box<InterfaceAccount<'info, Mint>>>,
+    #[account(
+        mut,
+        token::mint = config.protocol_mint,
+        token::authority = taker,
+    )]
+    pub taker_protocol_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        token::mint = config.protocol_mint,
+        token::authority = config.treasury,
+    )]
+    pub treasury_protocol_ata: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub protocol_token_program: Option<Interface<'info, TokenInterface>>,
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = pair_stats.bump
+    )]
+    pub pair_stats: Option<Account<'info, PairStats>>,
+    /// Current epoch's bucket for mint_b fees paid to the treasury, required when there is a fee.
+    #[account(
+        mut,
+        seeds = [b"fee_epoch", mint_b.key().as_ref(), fee_epoch.epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch.bump
+    )]
+    pub fee_epoch: Option<Box<Account<'info, FeeEpoch>>>,
+    /// The taker's points, credited for the mint_b paid at `points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", taker.key().as_ref()],
+        bump = taker_points.bump
+    )]
+    pub taker_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_b.key().as_ref()],
+        bump = points_rate.bump
+    )]
+    pub points_rate: Option<Box<Account<'info, PointsRate>>>,
+    /// The maker's points, credited for the mint_a filled at `maker_points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", maker.key().as_ref()],
+        bump = maker_points.bump
+    )]
+    pub maker_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_a.key().as_ref()],
+        bump = maker_points_rate.bump
+    )]
+    pub maker_points_rate: Option<Box<Account<'info, PointsRate>>>,
+    /// Ranks the maker by mint_b volume filled.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", mint_b.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    #[account(
+        mut,
+        seeds = [b"trade_log", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub trade_log: Option<AccountLoader<'info, TradeLog>>,
+    /// Receives the donation when `escrow.donation_recipient` is set.
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::authority = escrow.donation_recipient,
+        token::token_program = token_program,
+    )]
+    pub donation_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Receives the frontend fee when `escrow.frontend` is set.
+    #[account(
+        mut,
+        token::mint = mint_b,
+        token::authority = escrow.frontend,
+        token::token_program = token_program,
+    )]
+    pub frontend_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// When present, the taker insures the take by paying `config.insurance_premium_bps` of the
+    /// price on top into the pool's vault.
+    #[account(
+        mut,
+        seeds = [b"insurance_pool", mint_b.key().as_ref()],
+        bump = insurance_pool.bump
+    )]
+    pub insurance_pool: Option<Box<Account<'info, InsurancePool>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = insurance_pool,
+        associated_token::token_program = token_program,
+    )]
+    pub insurance_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// The taker's staked protocol tokens, which discount the take fee by `config.stake_tiers`.
+    #[account(
+        seeds = [b"fee_stake", taker.key().as_ref()],
+        bump = fee_stake.bump
+    )]
+    pub fee_stake: Option<Box<Account<'info, FeeStake>>>,
+    /// The taker's volume in mint_b. With `rebate_schedule` and `rebate_vault`, part of the
+    /// treasury's fee is accrued to it as a rebate.
+    #[account(
+        mut,
+        seeds = [b"taker_rebate", taker.key().as_ref(), mint_b.key().as_ref()],
+        bump = taker_rebate.bump
+    )]
+    pub taker_rebate: Option<Box<Account<'info, TakerRebate>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker_rebate,
+        associated_token::token_program = token_program,
+    )]
+    pub rebate_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        seeds = [b"rebate_schedule", mint_b.key().as_ref()],
+        bump = rebate_schedule.bump
+    )]
+    pub rebate_schedule: Option<Box<Account<'info, RebateSchedule>>>,
+    /// Reverse escrow opened with the maker's proceeds when `escrow.relist_receive` is set. The
+    /// payer funds it and is repaid with the taken escrow's rent.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Escrow::INIT_SPACE,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.relist_seed().as_ref()],
+        bump
+    )]
+    pub relist_escrow: Option<Box<Account<'info, Escrow>>>,
+    #[account(
+        init,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = relist_escrow,
+        associated_token::token_program = token_program,
+    )]
+    pub relist_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    pub memo_program: Option<Program<'info, Memo>>,
+    /// Receives the maker's payment when `escrow.unwrap_sol` is set and is closed straight after,
+    /// forwarding the SOL to the maker.
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"maker_wsol", escrow.key().as_ref()],
+        bump,
+        token::mint = mint_b,
+        token::authority = escrow,
+        token::token_program = token_program,
+    )]
+    pub maker_wsol: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
     pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: instructions sysvar, required when `config.cpi_allowlist` is set and the take is a CPI.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
     pub token_program: Interface<'info, TokenInterface>,
     pub system_program: Program<'info, System>,
 }
 
 impl<'info> Take<'info> {
-    pub fn deposit(&mut self) -> Result<()> {
+    pub fn check_active(&self) -> Result<()> {
+        self.check_taker()?;
+        require!(
+            self.escrow.co_signer == Pubkey::default()
+                || self
+                    .co_signer
+                    .as_ref()
+                    .is_some_and(|co_signer| co_signer.key() == self.escrow.co_signer),
+            EscrowError::MissingCoSigner
+        );
+        require!(
+            !self.escrow.require_compliance
+                || self
+                    .compliance_signer
+                    .as_ref()
+                    .is_some_and(|signer| signer.key() == self.config.compliance_signer),
+            EscrowError::MissingComplianceSignature
+        );
+        self.escrow.credential.check(
+            self.credential.as_ref().map(|info| info.as_ref()),
+            &self.taker.key(),
+        )?;
+        require!(
+            self.relist_escrow.is_some()
+                == (self.escrow.relist_receive > 0 && self.vault.amount > 0),
+            EscrowError::InvalidPayoutAccount
+        );
+        self.config
+            .check_cpi_caller(self.instructions.as_ref().map(|info| info.as_ref()))?;
+        self.escrow.check_takeable(
+            &self.taker.key(),
+            Clock::get()?.unix_timestamp,
+            &self.config,
+        )
+    }
+
+    fn uses_session(&self) -> bool {
+        !self.taker.to_account_info().is_signer
+    }
+
+    /// A session can only move tokens under its approval, so it can't wrap SOL or pay fees in
+    /// the protocol mint.
+    fn check_taker(&self) -> Result<()> {
+        if !self.uses_session() {
+            return Ok(());
+        }
+        let (Some(session_signer), Some(session_token)) =
+            (self.session_signer.as_ref(), self.session_token.as_ref())
+        else {
+            return err!(EscrowError::Unauthorized);
+        };
+        require!(
+            session_token.signer == session_signer.key()
+                && Clock::get()?.unix_timestamp < session_token.expires_at,
+            EscrowError::InvalidSession
+        );
+        require!(
+            self.taker_wsol.is_none() && self.protocol_fee_rate.is_none(),
+            EscrowError::InvalidSession
+        );
+        Ok(())
+    }
+
+    /// Authority over the taker's mint_b payment: the taker, or the session token as delegate.
+    fn payment_authority(&self) -> Result<AccountInfo<'info>> {
+        if !self.uses_session() {
+            return Ok(self.taker.to_account_info());
+        }
+        self.session_token
+            .as_ref()
+            .map(|session_token| session_token.to_account_info())
+            .ok_or(EscrowError::Unauthorized.into())
+    }
+
+    /// Price in the mint_b the taker chose to pay with.
+    pub fn receive(&self) -> Result<u64> {
+        self.escrow.receive_in(&self.mint_b.key())
+    }
+
+    fn unwraps_sol(&self) -> bool {
+        self.escrow.unwrap_sol && is_native_mint(&self.mint_b.key())
+    }
+
+    pub fn take_fee(&self) -> Result<u64> {
+        self.config.take_fee(
+            self.receive()?,
+            self.fee_override.as_deref(),
+            self.fee_stake.as_deref().map(|fee_stake| &**fee_stake),
+        )
+    }
+
+    pub fn protocol_fee(&self) -> Result<u64> {
+        let Some(protocol_fee_rate) = self.protocol_fee_rate.as_ref() else {
+            return Ok(0);
+        };
+
+        let fee = self.take_fee()?;
+        let discount = bps_of(fee, self.config.protocol_fee_discount_bps)?;
+        protocol_fee_rate.convert(fee.checked_sub(discount).ok_or(EscrowError::Overflow)?)
+    }
+
+    /// Rebate out of the treasury's share of the mint_b fee, by the volume the taker had already
+    /// filled.
+    pub fn rebate(&self) -> Result<u64> {
+        let (Some(taker_rebate), Some(rebate_schedule)) =
+            (self.taker_rebate.as_ref(), self.rebate_schedule.as_ref())
+        else {
+            return Ok(0);
+        };
+        bps_of(
+            self.mint_b_fee()?.saturating_sub(self.referral_fee()?),
+            rebate_schedule.rebate_bps(taker_rebate.volume),
+        )
+    }
+
+    pub fn mint_b_fee(&self) -> Result<u64> {
+        if self.protocol_fee_rate.is_some() {
+            return Ok(0);
+        }
+        self.take_fee()
+    }
+
+    pub fn pay_protocol_fee(&mut self) -> Result<()> {
+        let fee = self.protocol_fee()?;
+        if fee == 0 {
+            return Ok(());
+        }
+
+        let (Some(protocol_mint), Some(taker_protocol_ata), Some(protocol_token_program)) = (
+            self.protocol_mint.as_ref(),
+            self.taker_protocol_ata.as_ref(),
+            self.protocol_token_program.as_ref(),
+        ) else {
+            return err!(EscrowError::MissingFeeAccount);
+        };
+
+        if self.config.burn_protocol_fee {
+            let burn_accounts = Burn {
+                mint: protocol_mint.to_account_info(),
+                from: taker_protocol_ata.to_account_info(),
+                authority: self.taker.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(protocol_token_program.to_account_info(), burn_accounts);
+
+            return burn(cpi_ctx, fee);
+        }
+
+        let treasury_protocol_ata = self
+            .treasury_protocol_ata
+            .as_ref()
+            .ok_or(EscrowError::MissingFeeAccount)?;
+
         let transfer_accounts = TransferChecked {
-            from: self.taker_ata_b.to_account_info(),
-            mint: self.mint_b.to_account_info(),
-            to: self.maker_ata_b.to_account_info(),
+            from: taker_protocol_ata.to_account_info(),
+            mint: protocol_mint.to_account_info(),
+            to: treasury_protocol_ata.to_account_info(),
             authority: self.taker.to_account_info(),
         };
 
-        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+        let cpi_ctx = CpiContext::new(protocol_token_program.to_account_info(), transfer_accounts);
 
-        transfer_checked(cpi_ctx, self.escrow.receive, self.mint_b.decimals)
+        transfer_checked(cpi_ctx, fee, protocol_mint.decimals)
     }
 
-    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
+    pub fn referrer(&self) -> Option<Pubkey> {
+        match (&self.affiliate, &self.referrer_ata_b) {
+            (Some(affiliate), _) => Some(affiliate.owner),
+            (None, Some(referrer_ata_b)) => Some(referrer_ata_b.owner),
+            (None, None) => None,
+        }
+    }
+
+    pub fn referral_fee(&self) -> Result<u64> {
+        if self.referrer().is_none() {
+            return Ok(0);
+        }
+        bps_of(self.mint_b_fee()?, self.config.referral_share_bps)
+    }
+
+    fn payment_source(&self) -> Result<AccountInfo<'info>> {
+        match (&self.taker_wsol, &self.taker_ata_b) {
+            (Some(taker_wsol), _) => Ok(taker_wsol.to_account_info()),
+            (None, Some(taker_ata_b)) => Ok(taker_ata_b.to_account_info()),
+            (None, None) => err!(EscrowError::MissingPaymentAccount),
+        }
+    }
+
+    /// Funds `taker_wsol` with the taker's lamports so the payment can be made in native SOL.
+    pub fn wrap_for_taker(&mut self) -> Result<()> {
+        let Some(taker_wsol) = self.taker_wsol.as_ref() else {
+            return Ok(());
+        };
+        require!(
+            is_native_mint(&self.mint_b.key()),
+            EscrowError::NotNativeMint
+        );
+
+        let transfer_accounts = Transfer {
+            from: self.taker.to_account_info(),
+            to: taker_wsol.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        let lamports = self
+            .receive()?
+            .checked_add(self.insurance_premium()?)
+            .ok_or(EscrowError::Overflow)?;
+
+        transfer(cpi_ctx, lamports)?;
+
+        let sync_accounts = SyncNative {
+            account: taker_wsol.to_account_info(),
+        };
+
+        sync_native(CpiContext::new(
+            self.token_program.to_account_info(),
+            sync_accounts,
+        ))
+    }
+
+    pub fn close_taker_wsol(&mut self) -> Result<()> {
+        let Some(taker_wsol) = self.taker_wsol.as_ref() else {
+            return Ok(());
+        };
+
+        let accounts = CloseAccount {
+            account: taker_wsol.to_account_info(),
+            destination: self.payer.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        close_account(CpiContext::new(
+            self.token_program.to_account_info(),
+            accounts,
+        ))
+    }
+
+    /// Premium paid on top of the price when the taker insures the take.
+    pub fn insurance_premium(&self) -> Result<u64> {
+        if self.insurance_pool.is_none() {
+            return Ok(0);
+        }
+        self.config.insurance_premium(self.receive()?)
+    }
+
+    /// The escrow's frontend fee, taken from the price like the take fee.
+    pub fn frontend_fee(&self) -> Result<u64> {
+        self.escrow.frontend_fee(self.receive()?)
+    }
+
+    /// The price less the mint_b take fee and the frontend fee.
+    pub fn proceeds_after_fees(&self) -> Result<u64> {
+        let fees = self
+            .mint_b_fee()?
+            .checked_add(self.frontend_fee()?)
+            .ok_or(EscrowError::Overflow)?;
+        self.receive()?
+            .checked_sub(fees)
+            .ok_or(EscrowError::Overflow.into())
+    }
+
+    /// The escrow's donation, taken from the proceeds after fees.
+    pub fn donation(&self) -> Result<u64> {
+        self.escrow.donation(self.proceeds_after_fees()?)
+    }
+
+    /// Each active payout split's share of the maker's proceeds after fees.
+    pub fn split_amounts(&self) -> Result<Vec<(Pubkey, u64)>> {
+        self.escrow.split_amounts(self.proceeds_after_fees()?)
+    }
+
+    /// What the maker keeps after fees, payout splits and the donation.
+    pub fn maker_proceeds(&self) -> Result<u64> {
+        self.escrow.maker_proceeds(self.proceeds_after_fees()?)
+    }
+
+    /// Splits `remaining_accounts` into the mint_b accounts of the escrow's payout split
+    /// recipients, which come first, the take condition's account, which follows if the escrow
+    /// has a condition, and the rest.
+    #[allow(clippy::type_complexity)]
+    pub fn split_remaining<'a>(
+        &self,
+        remaining_accounts: &'a [AccountInfo<'info>],
+    ) -> Result<(
+        &'a [AccountInfo<'info>],
+        Option<&'a AccountInfo<'info>>,
+        &'a [AccountInfo<'info>],
+    )> {
+        let count = self.escrow.active_splits().count();
+        require_gte!(
+            remaining_accounts.len(),
+            count,
+            EscrowError::MissingPaymentAccount
+        );
+        let (split_accounts, rest) = remaining_accounts.split_at(count);
+        match rest.split_first() {
+            Some((condition_account, rest)) if self.escrow.condition.is_set() => {
+                Ok((split_accounts, Some(condition_account), rest))
+            }
+            _ => Ok((split_accounts, None, rest)),
+        }
+    }
+
+    pub fn check_condition(&self, account: Option<&AccountInfo<'info>>) -> Result<()> {
+        self.escrow.condition.check(account)
+    }
+
+    pub fn deposit(&mut self, split_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let fee = self.mint_b_fee()?;
+        let referral_fee = self.referral_fee()?;
+
+        let taker = self.taker.key();
+        let session_signer = self
+            .session_token
+            .as_ref()
+            .map(|session_token| session_token.signer)
+            .unwrap_or_default();
+        let session_bump = [self
+            .session_token
+            .as_ref()
+            .map_or(0, |session_token| session_token.bump)];
+        let session_seeds: [&[u8]; 4] = [
+            b"session_token",
+            taker.as_ref(),
+            session_signer.as_ref(),
+            &session_bump,
+        ];
+        let session_signer_seeds = [&session_seeds[..]];
+        let signer_seeds: &[&[&[u8]]] = if self.uses_session() {
+            &session_signer_seeds
+        } else {
+            &[]
+        };
+
+        if referral_fee > 0 {
+            let referral_destination = match &self.affiliate {
+                Some(_) => self
+                    .affiliate_vault
+                    .as_ref()
+                    .ok_or(EscrowError::MissingFeeAccount)?
+                    .to_account_info(),
+                None => self
+                    .referrer_ata_b
+                    .as_ref()
+                    .ok_or(EscrowError::MissingFeeAccount)?
+                    .to_account_info(),
+            };
+
+            let transfer_accounts = TransferChecked {
+                from: self.payment_source()?,
+                mint: self.mint_b.to_account_info(),
+                to: referral_destination,
+                authority: self.payment_authority()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+                .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, referral_fee, self.mint_b.decimals)?;
+
+            if let Some(affiliate) = self.affiliate.as_mut() {
+                affiliate.accrued = affiliate
+                    .accrued
+                    .checked_add(referral_fee)
+                    .ok_or(EscrowError::Overflow)?;
+                affiliate.referrals = affiliate
+                    .referrals
+                    .checked_add(1)
+                    .ok_or(EscrowError::Overflow)?;
+            }
+        }
+
+        let rebate = self.rebate()?;
+        let treasury_fee = fee
+            .checked_sub(
+                referral_fee
+                    .checked_add(rebate)
+                    .ok_or(EscrowError::Overflow)?,
+            )
+            .ok_or(EscrowError::Overflow)?;
+        if rebate > 0 {
+            let rebate_vault = self
+                .rebate_vault
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.payment_source()?,
+                mint: self.mint_b.to_account_info(),
+                to: rebate_vault.to_account_info(),
+                authority: self.payment_authority()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+                .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, rebate, self.mint_b.decimals)?;
+        }
+
+        if treasury_fee > 0 {
+            let treasury_ata_b = self
+                .treasury_ata_b
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.payment_source()?,
+                mint: self.mint_b.to_account_info(),
+                to: treasury_ata_b.to_account_info(),
+                authority: self.payment_authority()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+                .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, treasury_fee, self.mint_b.decimals)?;
+        }
+
+        for ((recipient, amount), account) in self.split_amounts()?.into_iter().zip(split_accounts)
+        {
+            require_keys_eq!(
+                *account.owner,
+                self.token_program.key(),
+                EscrowError::InvalidPayoutAccount
+            );
+            let destination = TokenAccount::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+            require!(
+                destination.mint == self.mint_b.key()
+                    && destination.owner == recipient
+                    && *account.key != self.vault.key(),
+                EscrowError::InvalidPayoutAccount
+            );
+            if amount == 0 {
+                continue;
+            }
+
+            let transfer_accounts = TransferChecked {
+                from: self.payment_source()?,
+                mint: self.mint_b.to_account_info(),
+                to: account.clone(),
+                authority: self.payment_authority()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+                .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, amount, self.mint_b.decimals)?;
+        }
+
+        let frontend_fee = self.frontend_fee()?;
+        if frontend_fee > 0 {
+            let frontend_ata_b = self
+                .frontend_ata_b
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.payment_source()?,
+                mint: self.mint_b.to_account_info(),
+                to: frontend_ata_b.to_account_info(),
+                authority: self.payment_authority()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+                .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, frontend_fee, self.mint_b.decimals)?;
+        }
+
+        let premium = self.insurance_premium()?;
+        if premium > 0 {
+            let insurance_vault = self
+                .insurance_vault
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.payment_source()?,
+                mint: self.mint_b.to_account_info(),
+                to: insurance_vault.to_account_info(),
+                authority: self.payment_authority()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+                .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, premium, self.mint_b.decimals)?;
+
+            if let Some(insurance_pool) = self.insurance_pool.as_mut() {
+                insurance_pool.premiums = insurance_pool
+                    .premiums
+                    .checked_add(premium)
+                    .ok_or(EscrowError::Overflow)?;
+            }
+        }
+
+        let donation = self.donation()?;
+        if donation > 0 {
+            let donation_ata_b = self
+                .donation_ata_b
+                .as_ref()
+                .ok_or(EscrowError::InvalidPayoutAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.payment_source()?,
+                mint: self.mint_b.to_account_info(),
+                to: donation_ata_b.to_account_info(),
+                authority: self.payment_authority()?,
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+                .with_signer(signer_seeds);
+
+            transfer_checked(cpi_ctx, donation, self.mint_b.decimals)?;
+        }
+
+        let maker_destination = if self.unwraps_sol() {
+            self.maker_wsol
+                .as_ref()
+                .ok_or(EscrowError::MissingWsolAccount)?
+                .to_account_info()
+        } else if let Some(relist_vault) = self.relist_vault.as_ref() {
+            relist_vault.to_account_info()
+        } else {
+            self.maker_ata_b.to_account_info()
+        };
+
+        let transfer_accounts = TransferChecked {
+            from: self.payment_source()?,
+            mint: self.mint_b.to_account_info(),
+            to: maker_destination,
+            authority: self.payment_authority()?,
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts)
+            .with_signer(signer_seeds);
+
+        transfer_checked(cpi_ctx, self.maker_proceeds()?, self.mint_b.decimals)
+    }
+
+    /// Closes `maker_wsol` into the payer, returning its rent, and forwards the unwrapped payment
+    /// to the maker.
+    pub fn unwrap_for_maker(&mut self) -> Result<()> {
+        if !self.unwraps_sol() {
+            return Ok(());
+        }
+        let Some(maker_wsol) = self.maker_wsol.as_ref() else {
+            return err!(EscrowError::MissingWsolAccount);
+        };
+
         let signer_seeds: [&[&[u8]]; 1] = [&[
             b"escrow",
             self.maker.to_account_info().key.as_ref(),
-            &self.escrow.seed.to_le_bytes()[..],
+            &self.escrow.seed[..],
             &[self.escrow.bump],
         ]];
 
-        let accounts = TransferChecked {
-            from: self.vault.to_account_info(),
-            mint: self.mint_a.to_account_info(),
-            to: self.taker_ata_a.to_account_info(),
+        let payment = self.maker_proceeds()?;
+
+        let accounts = CloseAccount {
+            account: maker_wsol.to_account_info(),
+            destination: self.payer.to_account_info(),
             authority: self.escrow.to_account_info(),
         };
 
@@ -95,7 +906,265 @@ impl<'info> Take<'info> {
             &signer_seeds,
         );
 
-        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+        close_account(ctx)?;
+
+        let transfer_accounts = Transfer {
+            from: self.payer.to_account_info(),
+            to: self.maker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.system_program.to_account_info(), transfer_accounts);
+
+        transfer(cpi_ctx, payment)
+    }
+
+    /// Opens the reverse escrow, selling the maker's proceeds for `escrow.relist_receive` of
+    /// mint_a. Only the maker's delegate and CPI setting carry over.
+    pub fn relist(&mut self, bumps: &TakeBumps) -> Result<()> {
+        if self.relist_escrow.is_none() {
+            return Ok(());
+        }
+        let vault = self
+            .relist_vault
+            .as_ref()
+            .ok_or(EscrowError::InvalidPayoutAccount)?
+            .key();
+
+        let now = Clock::get()?.unix_timestamp;
+        let seed = self.escrow.relist_seed();
+        let amount = self.maker_proceeds()?;
+        let relist_escrow = self
+            .relist_escrow
+            .as_mut()
+            .ok_or(EscrowError::InvalidPayoutAccount)?;
+        relist_escrow.set_inner(Escrow {
+            seed,
+            maker: self.maker.key(),
+            mint_a: self.mint_b.key(),
+            mint_b: self.mint_a.key(),
+            amount,
+            receive: self.escrow.relist_receive,
+            delegated: false,
+            note: self.escrow.note,
+            metadata_uri: [0; 128],
+            locked_until: 0,
+            starts_at: 0,
+            expires_at: if self.config.max_duration == 0 {
+                0
+            } else {
+                now.saturating_add(self.config.max_duration)
+            },
+            created_at: now,
+            frozen: false,
+            bump: bumps
+                .relist_escrow
+                .ok_or(EscrowError::InvalidPayoutAccount)?,
+            version: Escrow::VERSION,
+            unwrap_sol: false,
+            payment_options: [PaymentOption::default(); MAX_PAYMENT_OPTIONS],
+            allow_partial_fills: false,
+            price_tiers: [PriceTier::default(); MAX_PRICE_TIERS],
+            stream_duration: 0,
+            stream_taker: Pubkey::default(),
+            stream_started_at: 0,
+            streamed: 0,
+            delegate: self.escrow.delegate,
+            reject_cpi: self.escrow.reject_cpi,
+            allowed_takers: [Pubkey::default(); MAX_ALLOWED_TAKERS],
+            sealed_bids: false,
+            payout_splits: [PayoutSplit::default(); MAX_PAYOUT_SPLITS],
+            donation_recipient: Pubkey::default(),
+            donation_bps: 0,
+            relist_receive: 0,
+            condition: TakeCondition::default(),
+            tranche: 0,
+            fill_interval: 0,
+            last_fill_ts: 0,
+            co_signer: Pubkey::default(),
+            require_compliance: false,
+            credential: CredentialGate::default(),
+            frontend: Pubkey::default(),
+            frontend_fee_bps: 0,
+            vault,
+            last_updated_at: 0,
+            revision: 0,
+            reserved: [0; 64],
+        });
+
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+            maker_directory.insert(seed)?;
+        }
+
+        emit!(MakeEvent {
+            escrow: relist_escrow.key(),
+            maker: self.maker.key(),
+            mint_a: self.mint_b.key(),
+            mint_b: self.mint_a.key(),
+            deposit: amount,
+            receive: self.escrow.relist_receive,
+            note: self.escrow.note,
+        });
+        Ok(())
+    }
+
+    pub fn record_fill(&mut self) -> Result<()> {
+        let rebate = self.rebate()?;
+        let fill = Fill {
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            amount: self.vault.amount,
+            receive: self.receive()?,
+            treasury_fee: self
+                .mint_b_fee()?
+                .saturating_sub(self.referral_fee()?)
+                .saturating_sub(rebate),
+            rebate,
+        };
+
+        FillRecords {
+            trade_log: self.trade_log.as_ref(),
+            pair_stats: self.pair_stats.as_deref_mut(),
+            fee_epoch: self
+                .fee_epoch
+                .as_deref_mut()
+                .map(|fee_epoch| &mut **fee_epoch),
+            maker_points: self
+                .maker_points
+                .as_deref_mut()
+                .map(|maker_points| &mut **maker_points),
+            maker_points_rate: self
+                .maker_points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            taker_points: self
+                .taker_points
+                .as_deref_mut()
+                .map(|taker_points| &mut **taker_points),
+            points_rate: self
+                .points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            leaderboard: self
+                .leaderboard
+                .as_deref_mut()
+                .map(|leaderboard| &mut **leaderboard),
+            taker_rebate: self
+                .taker_rebate
+                .as_deref_mut()
+                .map(|taker_rebate| &mut **taker_rebate),
+        }
+        .record(&fill)
+    }
+
+    pub fn memo(&self) -> Result<()> {
+        let Some(memo_program) = self.memo_program.as_ref() else {
+            return Ok(());
+        };
+
+        let memo = format!(
+            "escrow:{} take:{}:{}",
+            self.escrow.key(),
+            self.vault.amount,
+            self.receive()?
+        );
+
+        build_memo(
+            CpiContext::new(memo_program.to_account_info(), BuildMemo {}),
+            memo.as_bytes(),
+        )
+    }
+
+    /// Posts the settlement to Wormhole when its accounts are passed as remaining accounts.
+    pub fn post_settlement(&self, remaining_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        if remaining_accounts.is_empty() {
+            return Ok(());
+        }
+        let wormhole = WormholeAccounts::from_remaining(remaining_accounts)?;
+
+        let payload = SettlementMessage {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            mint_a: self.mint_a.key(),
+            mint_b: self.mint_b.key(),
+            amount: self.vault.amount,
+            receive: self.receive()?,
+        };
+
+        wormhole.post_message(
+            &self.escrow.key(),
+            &payload,
+            &self.payer.to_account_info(),
+            &self.system_program.to_account_info(),
+        )
+    }
+
+    pub fn withdraw_and_close_vault(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        if self.vault.amount == 0 {
+            emit!(VaultDrainedEvent {
+                escrow: self.escrow.key(),
+                maker: self.maker.key(),
+                expected: self.escrow.amount,
+            });
+        } else if self.escrow.stream_duration > 0 {
+            // The deposit stays in the vault and unlocks through `claim_stream`.
+            self.escrow.stream_taker = self.taker.key();
+            self.escrow.stream_started_at = Clock::get()?.unix_timestamp;
+
+            emit!(TakeEvent {
+                escrow: self.escrow.key(),
+                maker: self.maker.key(),
+                taker: self.taker.key(),
+                amount: self.vault.amount,
+                receive: self.receive()?,
+                fee: self.mint_b_fee()?,
+                protocol_fee: self.protocol_fee()?,
+                referrer: self.referrer(),
+                referral_fee: self.referral_fee()?,
+                donation: self.donation()?,
+                frontend_fee: self.frontend_fee()?,
+                note: self.escrow.note,
+            });
+            return Ok(());
+        } else {
+            let accounts = TransferChecked {
+                from: self.vault.to_account_info(),
+                mint: self.mint_a.to_account_info(),
+                to: self.taker_ata_a.to_account_info(),
+                authority: self.escrow.to_account_info(),
+            };
+
+            let ctx = CpiContext::new_with_signer(
+                self.token_program.to_account_info(),
+                accounts,
+                &signer_seeds,
+            );
+
+            transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+
+            emit!(TakeEvent {
+                escrow: self.escrow.key(),
+                maker: self.maker.key(),
+                taker: self.taker.key(),
+                amount: self.vault.amount,
+                receive: self.receive()?,
+                fee: self.mint_b_fee()?,
+                protocol_fee: self.protocol_fee()?,
+                referrer: self.referrer(),
+                referral_fee: self.referral_fee()?,
+                donation: self.donation()?,
+                frontend_fee: self.frontend_fee()?,
+                note: self.escrow.note,
+            });
+        }
 
         let accounts = CloseAccount {
             account: self.vault.to_account_info(),
@@ -109,6 +1178,18 @@ impl<'info> Take<'info> {
             &signer_seeds,
         );
 
-        close_account(ctx)
+        close_account(ctx)?;
+
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+        }
+
+        // A relist was funded by the payer, who takes this escrow's rent in return.
+        let rent_destination = if self.relist_escrow.is_some() {
+            self.payer.to_account_info()
+        } else {
+            self.maker.to_account_info()
+        };
+        self.escrow.close(rent_destination)
     }
 }
@@ -0,0 +1,139 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    accepts_public_credits, CompressedOffer, CompressedSettleEvent, EscrowError, OfferTree,
+};
+
+#[derive(Accounts)]
+#[instruction(index: u32, offer: CompressedOffer)]
+pub struct TakeCompressed<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        address = offer.maker
+    )]
+    pub maker: SystemAccount<'info>,
+    #[account(
+        address = offer.mint_a
+    )]
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        address = offer.mint_b
+    )]
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+        constraint = !taker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&taker_ata_a.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+        constraint = !maker_ata_b.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&maker_ata_b.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(mut)]
+    pub offer_tree: AccountLoader<'info, OfferTree>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = offer_tree,
+        associated_token::token_program = token_program,
+    )]
+    pub tree_vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeCompressed<'info> {
+    pub fn settle(
+        &mut self,
+        index: u32,
+        offer: &CompressedOffer,
+        proof: &[[u8; 32]],
+    ) -> Result<()> {
+        self.offer_tree.load_mut()?.settle(index, offer, proof)?;
+
+        emit!(CompressedSettleEvent {
+            offer_tree: self.offer_tree.key(),
+            index,
+            taker: Some(self.taker.key()),
+        });
+        Ok(())
+    }
+
+    pub fn deposit(&mut self, offer: &CompressedOffer) -> Result<()> {
+        let transfer_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, offer.receive, self.mint_b.decimals)
+    }
+
+    pub fn withdraw(&mut self, offer: &CompressedOffer) -> Result<()> {
+        let (id, bump) = {
+            let offer_tree = self.offer_tree.load()?;
+            (offer_tree.id, offer_tree.bump)
+        };
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"offer_tree", &id.to_le_bytes(), &[bump]]];
+
+        let accounts = TransferChecked {
+            from: self.tree_vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.offer_tree.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, offer.amount, self.mint_a.decimals)
+    }
+}
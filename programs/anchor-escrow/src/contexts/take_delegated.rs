@@ -0,0 +1,312 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as instructions_sysvar};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{
+    accepts_public_credits, Config, Escrow, EscrowError, FeeEpoch, FeeOverride, FeeStake, Fill,
+    FillRecords, Leaderboard, MakerDirectory, PairStats, Points, PointsRate, TakeEvent,
+};
+
+#[derive(Accounts)]
+pub struct TakeDelegated<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+    )]
+    pub maker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+        constraint = !taker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&taker_ata_a.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+        constraint = !maker_ata_b.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&maker_ata_b.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = escrow.delegated @ EscrowError::InvalidEscrowMode,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [b"fee_override", escrow.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+    /// The taker's staked protocol tokens, which discount the take fee by `config.stake_tiers`.
+    #[account(
+        seeds = [b"fee_stake", taker.key().as_ref()],
+        bump = fee_stake.bump
+    )]
+    pub fee_stake: Option<Box<Account<'info, FeeStake>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = config.treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    /// Current epoch's bucket for mint_b fees paid to the treasury, required when there is a fee.
+    #[account(
+        mut,
+        seeds = [b"fee_epoch", mint_b.key().as_ref(), fee_epoch.epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch.bump
+    )]
+    pub fee_epoch: Option<Box<Account<'info, FeeEpoch>>>,
+    /// Ranks the maker by mint_b volume filled.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", mint_b.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = pair_stats.bump
+    )]
+    pub pair_stats: Option<Box<Account<'info, PairStats>>>,
+    /// The maker's points, credited for the mint_a filled at `maker_points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", maker.key().as_ref()],
+        bump = maker_points.bump
+    )]
+    pub maker_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_a.key().as_ref()],
+        bump = maker_points_rate.bump
+    )]
+    pub maker_points_rate: Option<Box<Account<'info, PointsRate>>>,
+    /// The taker's points, credited for the mint_b paid at `points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", taker.key().as_ref()],
+        bump = taker_points.bump
+    )]
+    pub taker_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_b.key().as_ref()],
+        bump = points_rate.bump
+    )]
+    pub points_rate: Option<Box<Account<'info, PointsRate>>>,
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: instructions sysvar, required when `config.cpi_allowlist` is set and the take is a CPI.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeDelegated<'info> {
+    pub fn unlist(&mut self) -> Result<()> {
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&self.escrow.seed);
+        }
+        Ok(())
+    }
+
+    pub fn check_active(&self) -> Result<()> {
+        self.config
+            .check_cpi_caller(self.instructions.as_ref().map(|info| info.as_ref()))?;
+        self.escrow.check_takeable(
+            &self.taker.key(),
+            Clock::get()?.unix_timestamp,
+            &self.config,
+        )
+    }
+
+    pub fn take_fee(&self) -> Result<u64> {
+        self.config.take_fee(
+            self.escrow.receive,
+            self.fee_override.as_deref(),
+            self.fee_stake.as_deref().map(|fee_stake| &**fee_stake),
+        )
+    }
+
+    pub fn deposit(&mut self) -> Result<()> {
+        let fee = self.take_fee()?;
+
+        if fee > 0 {
+            let treasury_ata_b = self
+                .treasury_ata_b
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.taker_ata_b.to_account_info(),
+                mint: self.mint_b.to_account_info(),
+                to: treasury_ata_b.to_account_info(),
+                authority: self.taker.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+            transfer_checked(cpi_ctx, fee, self.mint_b.decimals)?;
+        }
+
+        let transfer_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(
+            cpi_ctx,
+            self.escrow
+                .receive
+                .checked_sub(fee)
+                .ok_or(EscrowError::Overflow)?,
+            self.mint_b.decimals,
+        )
+    }
+
+    pub fn record_fill(&mut self) -> Result<()> {
+        let fill = Fill {
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            amount: self.escrow.amount,
+            receive: self.escrow.receive,
+            treasury_fee: self.take_fee()?,
+            rebate: 0,
+        };
+
+        FillRecords {
+            pair_stats: self
+                .pair_stats
+                .as_deref_mut()
+                .map(|pair_stats| &mut **pair_stats),
+            fee_epoch: self
+                .fee_epoch
+                .as_deref_mut()
+                .map(|fee_epoch| &mut **fee_epoch),
+            maker_points: self
+                .maker_points
+                .as_deref_mut()
+                .map(|maker_points| &mut **maker_points),
+            maker_points_rate: self
+                .maker_points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            taker_points: self
+                .taker_points
+                .as_deref_mut()
+                .map(|taker_points| &mut **taker_points),
+            points_rate: self
+                .points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            leaderboard: self
+                .leaderboard
+                .as_deref_mut()
+                .map(|leaderboard| &mut **leaderboard),
+            ..Default::default()
+        }
+        .record(&fill)
+    }
+
+    pub fn withdraw(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.escrow.seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.maker_ata_a.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.escrow.amount, self.mint_a.decimals)?;
+
+        emit!(TakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            amount: self.escrow.amount,
+            receive: self.escrow.receive,
+            fee: self.take_fee()?,
+            protocol_fee: 0,
+            referrer: None,
+            referral_fee: 0,
+            donation: 0,
+            frontend_fee: 0,
+            note: self.escrow.note,
+        });
+        Ok(())
+    }
+}
@@ -0,0 +1,469 @@
+use anchor_lang::{prelude::*, solana_program::sysvar::instructions as instructions_sysvar};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{
+    accepts_public_credits, bps_of, Config, Escrow, EscrowError, FeeEpoch, FeeOverride, FeeStake,
+    Fill, FillRecords, Leaderboard, MakerDirectory, PairStats, Points, PointsRate, RebateSchedule,
+    TakeEvent, TakerRebate, TradeLog,
+};
+
+/// Buys `fill` of the deposit at the tiered pro-rata price, leaving the rest of the escrow open.
+/// A fill that would leave dust is widened to the whole remainder, and fails if that costs more
+/// than the taker's `max_in`. The escrow and vault close once the deposit is fully filled. Also
+/// used by `take_exact_out`, where the taker names the mint_a amount and a ceiling on the mint_b
+/// price. Scheduled escrows are filled one tranche per interval. Each fill is recorded like a
+/// full `Take`.
+#[derive(Accounts)]
+pub struct TakePartial<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_a: Box<InterfaceAccount<'info, Mint>>,
+    pub mint_b: Box<InterfaceAccount<'info, Mint>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_a,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+        constraint = !taker_ata_a.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&taker_ata_a.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub taker_ata_a: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+        constraint = !maker_ata_b.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&maker_ata_b.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        has_one = maker,
+        has_one = mint_a,
+        has_one = mint_b,
+        constraint = !escrow.delegated @ EscrowError::InvalidEscrowMode,
+        constraint = escrow.allow_partial_fills @ EscrowError::InvalidFillAmount,
+        seeds = [b"escrow", maker.key().as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Box<Account<'info, Escrow>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = escrow,
+        associated_token::token_program = token_program,
+        constraint = escrow.holds_vault(&vault.key()) @ EscrowError::InvalidVault,
+    )]
+    pub vault: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Box<Account<'info, Config>>,
+    #[account(
+        seeds = [b"fee_override", escrow.key().as_ref()],
+        bump = fee_override.bump
+    )]
+    pub fee_override: Option<Account<'info, FeeOverride>>,
+    /// The taker's staked protocol tokens, which discount the take fee by `config.stake_tiers`.
+    #[account(
+        seeds = [b"fee_stake", taker.key().as_ref()],
+        bump = fee_stake.bump
+    )]
+    pub fee_stake: Option<Box<Account<'info, FeeStake>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = config.treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata_b: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        mut,
+        seeds = [b"pair_stats", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump = pair_stats.bump
+    )]
+    pub pair_stats: Option<Box<Account<'info, PairStats>>>,
+    /// Current epoch's bucket for mint_b fees paid to the treasury, required when there is a fee.
+    #[account(
+        mut,
+        seeds = [b"fee_epoch", mint_b.key().as_ref(), fee_epoch.epoch.to_le_bytes().as_ref()],
+        bump = fee_epoch.bump
+    )]
+    pub fee_epoch: Option<Box<Account<'info, FeeEpoch>>>,
+    /// The taker's points, credited for the mint_b paid at `points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", taker.key().as_ref()],
+        bump = taker_points.bump
+    )]
+    pub taker_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_b.key().as_ref()],
+        bump = points_rate.bump
+    )]
+    pub points_rate: Option<Box<Account<'info, PointsRate>>>,
+    /// The maker's points, credited for the mint_a filled at `maker_points_rate`.
+    #[account(
+        mut,
+        seeds = [b"points", maker.key().as_ref()],
+        bump = maker_points.bump
+    )]
+    pub maker_points: Option<Box<Account<'info, Points>>>,
+    #[account(
+        seeds = [b"points_rate", mint_a.key().as_ref()],
+        bump = maker_points_rate.bump
+    )]
+    pub maker_points_rate: Option<Box<Account<'info, PointsRate>>>,
+    /// Ranks the maker by mint_b volume filled.
+    #[account(
+        mut,
+        seeds = [b"leaderboard", mint_b.key().as_ref()],
+        bump = leaderboard.bump
+    )]
+    pub leaderboard: Option<Box<Account<'info, Leaderboard>>>,
+    /// The taker's volume in mint_b. With `rebate_schedule` and `rebate_vault`, part of the
+    /// treasury's fee is accrued to it as a rebate.
+    #[account(
+        mut,
+        seeds = [b"taker_rebate", taker.key().as_ref(), mint_b.key().as_ref()],
+        bump = taker_rebate.bump
+    )]
+    pub taker_rebate: Option<Box<Account<'info, TakerRebate>>>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker_rebate,
+        associated_token::token_program = token_program,
+    )]
+    pub rebate_vault: Option<Box<InterfaceAccount<'info, TokenAccount>>>,
+    #[account(
+        seeds = [b"rebate_schedule", mint_b.key().as_ref()],
+        bump = rebate_schedule.bump
+    )]
+    pub rebate_schedule: Option<Box<Account<'info, RebateSchedule>>>,
+    #[account(
+        mut,
+        seeds = [b"trade_log", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump
+    )]
+    pub trade_log: Option<AccountLoader<'info, TradeLog>>,
+    #[account(
+        mut,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Option<Account<'info, MakerDirectory>>,
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    /// CHECK: instructions sysvar, required when `config.cpi_allowlist` is set and the take is a CPI.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: Option<UncheckedAccount<'info>>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakePartial<'info> {
+    pub fn check_active(&self) -> Result<()> {
+        self.config
+            .check_cpi_caller(self.instructions.as_ref().map(|info| info.as_ref()))?;
+        self.escrow.check_takeable(
+            &self.taker.key(),
+            Clock::get()?.unix_timestamp,
+            &self.config,
+        )
+    }
+
+    /// Widens `fill` to the whole remainder if it would otherwise leave dust behind.
+    pub fn sweep_dust(&self, fill: u64) -> u64 {
+        self.escrow.sweep_dust(fill, self.config.dust_threshold)
+    }
+
+    /// Scheduled escrows only take their next tranche, and only once `fill_interval` has passed
+    /// since the previous fill.
+    pub fn check_schedule(&self, fill: u64) -> Result<()> {
+        if !self.escrow.is_scheduled() {
+            return Ok(());
+        }
+        let tranche = self.sweep_dust(self.escrow.tranche.min(self.escrow.amount));
+        require!(fill == tranche, EscrowError::InvalidFillAmount);
+        require!(
+            self.escrow.last_fill_ts == 0
+                || Clock::get()?.unix_timestamp
+                    >= self
+                        .escrow
+                        .last_fill_ts
+                        .saturating_add(self.escrow.fill_interval),
+            EscrowError::TrancheNotDue
+        );
+        Ok(())
+    }
+
+    /// Price of `fill`, failing if it is above `max_in`.
+    pub fn capped_price(&self, fill: u64, max_in: u64) -> Result<u64> {
+        let price = self.escrow.fill_price(fill)?;
+        require!(price <= max_in, EscrowError::PriceTooHigh);
+        Ok(price)
+    }
+
+    /// Price of exactly `amount_out` mint_a units, failing if it is above `max_in`. Fills that
+    /// would leave dust are rejected rather than widened, so the taker never buys more than asked.
+    pub fn exact_out_price(&self, amount_out: u64, max_in: u64) -> Result<u64> {
+        require!(
+            self.sweep_dust(amount_out) == amount_out,
+            EscrowError::InvalidFillAmount
+        );
+        self.capped_price(amount_out, max_in)
+    }
+
+    pub fn take_fee(&self, price: u64) -> Result<u64> {
+        self.config.take_fee(
+            price,
+            self.fee_override.as_deref(),
+            self.fee_stake.as_deref().map(|fee_stake| &**fee_stake),
+        )
+    }
+
+    /// Rebate out of the treasury's share of the fee, by the volume the taker had already filled.
+    pub fn rebate(&self, price: u64) -> Result<u64> {
+        let (Some(taker_rebate), Some(rebate_schedule)) =
+            (self.taker_rebate.as_ref(), self.rebate_schedule.as_ref())
+        else {
+            return Ok(0);
+        };
+        bps_of(
+            self.take_fee(price)?,
+            rebate_schedule.rebate_bps(taker_rebate.volume),
+        )
+    }
+
+    pub fn deposit(&mut self, price: u64) -> Result<()> {
+        let fee = self.take_fee(price)?;
+        let rebate = self.rebate(price)?;
+
+        if rebate > 0 {
+            let rebate_vault = self
+                .rebate_vault
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.taker_ata_b.to_account_info(),
+                mint: self.mint_b.to_account_info(),
+                to: rebate_vault.to_account_info(),
+                authority: self.taker.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+            transfer_checked(cpi_ctx, rebate, self.mint_b.decimals)?;
+        }
+
+        if fee > rebate {
+            let treasury_ata_b = self
+                .treasury_ata_b
+                .as_ref()
+                .ok_or(EscrowError::MissingFeeAccount)?;
+
+            let transfer_accounts = TransferChecked {
+                from: self.taker_ata_b.to_account_info(),
+                mint: self.mint_b.to_account_info(),
+                to: treasury_ata_b.to_account_info(),
+                authority: self.taker.to_account_info(),
+            };
+
+            let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+            transfer_checked(
+                cpi_ctx,
+                fee.checked_sub(rebate).ok_or(EscrowError::Overflow)?,
+                self.mint_b.decimals,
+            )?;
+        }
+
+        let transfer_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(
+            cpi_ctx,
+            price.checked_sub(fee).ok_or(EscrowError::Overflow)?,
+            self.mint_b.decimals,
+        )
+    }
+
+    pub fn withdraw(&mut self, fill: u64, price: u64) -> Result<()> {
+        let seed = self.escrow.seed;
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &seed[..],
+            &[self.escrow.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.taker_ata_a.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        // The final fill takes whatever the vault holds so that it can close.
+        let withdrawn = if fill == self.escrow.amount {
+            self.vault.amount
+        } else {
+            fill
+        };
+
+        transfer_checked(ctx, withdrawn, self.mint_a.decimals)?;
+
+        emit!(TakeEvent {
+            escrow: self.escrow.key(),
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            amount: withdrawn,
+            receive: price,
+            fee: self.take_fee(price)?,
+            protocol_fee: 0,
+            referrer: None,
+            referral_fee: 0,
+            donation: 0,
+            frontend_fee: 0,
+            note: self.escrow.note,
+        });
+
+        self.record_fill(withdrawn, price)?;
+
+        // Remaining terms keep the original unit price; tier discounts are per fill.
+        let base = self.escrow.base_price(fill)?;
+        self.escrow.amount = self
+            .escrow
+            .amount
+            .checked_sub(fill)
+            .ok_or(EscrowError::Overflow)?;
+        self.escrow.receive = self
+            .escrow
+            .receive
+            .checked_sub(base)
+            .ok_or(EscrowError::Overflow)?;
+        self.escrow.last_fill_ts = Clock::get()?.unix_timestamp;
+
+        if self.escrow.amount > 0 {
+            return Ok(());
+        }
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.maker.to_account_info(),
+            authority: self.escrow.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)?;
+
+        if let Some(maker_directory) = self.maker_directory.as_mut() {
+            maker_directory.remove(&seed);
+        }
+
+        self.escrow.close(self.maker.to_account_info())
+    }
+
+    pub fn record_fill(&mut self, amount: u64, price: u64) -> Result<()> {
+        let rebate = self.rebate(price)?;
+        let fill = Fill {
+            maker: self.maker.key(),
+            taker: self.taker.key(),
+            amount,
+            receive: price,
+            treasury_fee: self.take_fee(price)?.saturating_sub(rebate),
+            rebate,
+        };
+
+        FillRecords {
+            trade_log: self.trade_log.as_ref(),
+            pair_stats: self
+                .pair_stats
+                .as_deref_mut()
+                .map(|pair_stats| &mut **pair_stats),
+            fee_epoch: self
+                .fee_epoch
+                .as_deref_mut()
+                .map(|fee_epoch| &mut **fee_epoch),
+            maker_points: self
+                .maker_points
+                .as_deref_mut()
+                .map(|maker_points| &mut **maker_points),
+            maker_points_rate: self
+                .maker_points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            taker_points: self
+                .taker_points
+                .as_deref_mut()
+                .map(|taker_points| &mut **taker_points),
+            points_rate: self
+                .points_rate
+                .as_deref()
+                .map(|points_rate| &**points_rate),
+            leaderboard: self
+                .leaderboard
+                .as_deref_mut()
+                .map(|leaderboard| &mut **leaderboard),
+            taker_rebate: self
+                .taker_rebate
+                .as_deref_mut()
+                .map(|taker_rebate| &mut **taker_rebate),
+        }
+        .record(&fill)
+    }
+}
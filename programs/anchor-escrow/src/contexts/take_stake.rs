@@ -0,0 +1,123 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        program::invoke_signed,
+        stake::{self, state::StakeAuthorize},
+    },
+};
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{accepts_public_credits, EscrowError, StakeEscrow};
+
+#[derive(Accounts)]
+pub struct TakeStake<'info> {
+    #[account(mut)]
+    pub taker: Signer<'info>,
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub maker: SystemAccount<'info>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = taker,
+        associated_token::token_program = token_program,
+    )]
+    pub taker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = maker,
+        associated_token::token_program = token_program,
+        constraint = !maker_ata_b.is_frozen() @ EscrowError::TokenAccountFrozen,
+        constraint = accepts_public_credits(&maker_ata_b.to_account_info()) @ EscrowError::PublicCreditsDisabled,
+    )]
+    pub maker_ata_b: Box<InterfaceAccount<'info, TokenAccount>>,
+    #[account(
+        mut,
+        close = maker,
+        has_one = maker,
+        has_one = mint_b,
+        seeds = [b"stake_escrow", maker.key().as_ref(), stake_escrow.seed.as_ref()],
+        bump = stake_escrow.bump
+    )]
+    pub stake_escrow: Account<'info, StakeEscrow>,
+    /// CHECK: address is bound to the escrow.
+    #[account(
+        mut,
+        address = stake_escrow.stake
+    )]
+    pub stake_account: UncheckedAccount<'info>,
+    pub clock: Sysvar<'info, Clock>,
+    /// CHECK: the stake program.
+    #[account(address = stake::program::ID)]
+    pub stake_program: UncheckedAccount<'info>,
+    /// CHECK: the taker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", taker.key().as_ref()],
+        bump,
+        constraint = taker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub taker_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: the maker's denylist entry, which must not exist.
+    #[account(
+        seeds = [b"denylist", maker.key().as_ref()],
+        bump,
+        constraint = maker_denylist_entry.data_is_empty() @ EscrowError::AddressDenied,
+    )]
+    pub maker_denylist_entry: UncheckedAccount<'info>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> TakeStake<'info> {
+    pub fn deposit(&mut self) -> Result<()> {
+        let transfer_accounts = TransferChecked {
+            from: self.taker_ata_b.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.maker_ata_b.to_account_info(),
+            authority: self.taker.to_account_info(),
+        };
+
+        let cpi_ctx = CpiContext::new(self.token_program.to_account_info(), transfer_accounts);
+
+        transfer_checked(cpi_ctx, self.stake_escrow.receive, self.mint_b.decimals)
+    }
+
+    pub fn transfer_authorities(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"stake_escrow",
+            self.maker.to_account_info().key.as_ref(),
+            &self.stake_escrow.seed[..],
+            &[self.stake_escrow.bump],
+        ]];
+
+        for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+            let ix = stake::instruction::authorize(
+                self.stake_account.key,
+                &self.stake_escrow.key(),
+                &self.taker.key(),
+                stake_authorize,
+                None,
+            );
+
+            invoke_signed(
+                &ix,
+                &[
+                    self.stake_account.to_account_info(),
+                    self.clock.to_account_info(),
+                    self.stake_escrow.to_account_info(),
+                ],
+                &signer_seeds,
+            )?;
+        }
+        Ok(())
+    }
+}
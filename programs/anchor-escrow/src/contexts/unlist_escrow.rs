@@ -0,0 +1,23 @@
+use anchor_lang::prelude::*;
+
+use crate::MakerDirectory;
+
+/// Removes a seed from the maker's directory, e.g. for an escrow closed without it.
+#[derive(Accounts)]
+pub struct UnlistEscrow<'info> {
+    pub maker: Signer<'info>,
+    #[account(
+        mut,
+        has_one = maker,
+        seeds = [b"maker_directory", maker.key().as_ref()],
+        bump = maker_directory.bump
+    )]
+    pub maker_directory: Account<'info, MakerDirectory>,
+}
+
+impl<'info> UnlistEscrow<'info> {
+    pub fn unlist_escrow(&mut self, seed: [u8; 32]) -> Result<()> {
+        self.maker_directory.remove(&seed);
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked},
+};
+
+use crate::{EscrowError, FeeStake};
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+    pub protocol_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = owner,
+        associated_token::mint = protocol_mint,
+        associated_token::authority = owner,
+        associated_token::token_program = token_program
+    )]
+    pub owner_ata: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        has_one = owner,
+        seeds = [b"fee_stake", owner.key().as_ref()],
+        bump = fee_stake.bump
+    )]
+    pub fee_stake: Account<'info, FeeStake>,
+    #[account(
+        mut,
+        associated_token::mint = protocol_mint,
+        associated_token::authority = fee_stake,
+        associated_token::token_program = token_program
+    )]
+    pub stake_vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> Unstake<'info> {
+    pub fn unstake(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount > 0 && amount <= self.fee_stake.amount,
+            EscrowError::InvalidStakeAmount
+        );
+
+        let owner = self.owner.key();
+        let signer_seeds: [&[&[u8]]; 1] = [&[b"fee_stake", owner.as_ref(), &[self.fee_stake.bump]]];
+
+        let accounts = TransferChecked {
+            from: self.stake_vault.to_account_info(),
+            mint: self.protocol_mint.to_account_info(),
+            to: self.owner_ata.to_account_info(),
+            authority: self.fee_stake.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, amount, self.protocol_mint.decimals)?;
+
+        self.fee_stake.amount = self
+            .fee_stake
+            .amount
+            .checked_sub(amount)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+}
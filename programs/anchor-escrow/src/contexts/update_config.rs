@@ -0,0 +1,28 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, ConfigParams, EscrowError};
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        mut,
+        has_one = admin @ EscrowError::Unauthorized,
+        constraint = config.is_admin_invocation_allowed() @ EscrowError::AdminCpiRequired,
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateConfig<'info> {
+    pub fn update_config(&mut self, params: ConfigParams) -> Result<()> {
+        params.validate()?;
+        require!(
+            self.config.timelock == 0 || !self.config.requires_timelock(&params),
+            EscrowError::TimelockRequired
+        );
+        self.config.apply(&params);
+        Ok(())
+    }
+}
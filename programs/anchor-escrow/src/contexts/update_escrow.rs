@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{Config, Escrow, EscrowError};
+
+/// Reprices an escrow in mint_b. Signed by the maker or their delegate.
+#[derive(Accounts)]
+pub struct UpdateEscrow<'info> {
+    pub authority: Signer<'info>,
+    #[account(
+        mut,
+        constraint = escrow.is_manager(&authority.key()) @ EscrowError::Unauthorized,
+        constraint = !escrow.is_streaming() @ EscrowError::AlreadyTaken,
+        seeds = [b"escrow", escrow.maker.as_ref(), escrow.seed.as_ref()],
+        bump = escrow.bump
+    )]
+    pub escrow: Account<'info, Escrow>,
+    #[account(
+        seeds = [b"config"],
+        bump = config.bump
+    )]
+    pub config: Account<'info, Config>,
+}
+
+impl<'info> UpdateEscrow<'info> {
+    pub fn update_escrow(&mut self, receive: u64) -> Result<()> {
+        if self.config.max_receive != 0 {
+            require_gte!(
+                self.config.max_receive,
+                receive,
+                EscrowError::ReceiveTooLarge
+            );
+        }
+        self.escrow.receive = receive;
+        self.escrow.record_update(Clock::get()?.unix_timestamp);
+        Ok(())
+    }
+}
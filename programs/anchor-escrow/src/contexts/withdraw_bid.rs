@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{Bid, EscrowError};
+
+/// Returns a bid to its bidder. The bidder may withdraw once the bid's lock has passed, and
+/// anyone may return it once the escrow has closed, so losing bids can be refunded by a crank.
+#[derive(Accounts)]
+pub struct WithdrawBid<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(mut)]
+    pub bidder: SystemAccount<'info>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        associated_token::mint = mint_b,
+        associated_token::authority = bidder,
+        associated_token::token_program = token_program
+    )]
+    pub bidder_ata_b: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: closed once the escrow is taken or refunded; only its emptiness is read.
+    #[account(
+        address = bid.escrow
+    )]
+    pub escrow: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = bidder,
+        has_one = bidder,
+        seeds = [b"bid", bid.escrow.as_ref(), bidder.key().as_ref()],
+        bump = bid.bump
+    )]
+    pub bid: Account<'info, Bid>,
+    #[account(
+        mut,
+        associated_token::mint = mint_b,
+        associated_token::authority = bid,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> WithdrawBid<'info> {
+    pub fn check_withdrawable(&self) -> Result<()> {
+        if self.escrow.data_is_empty() {
+            return Ok(());
+        }
+        require_keys_eq!(
+            self.payer.key(),
+            self.bidder.key(),
+            EscrowError::Unauthorized
+        );
+        require!(
+            Clock::get()?.unix_timestamp >= self.bid.locked_until,
+            EscrowError::RefundLocked
+        );
+        Ok(())
+    }
+
+    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"bid",
+            self.bid.escrow.as_ref(),
+            self.bidder.to_account_info().key.as_ref(),
+            &[self.bid.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_b.to_account_info(),
+            to: self.bidder_ata_b.to_account_info(),
+            authority: self.bid.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.vault.amount, self.mint_b.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.bidder.to_account_info(),
+            authority: self.bid.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+}
@@ -0,0 +1,114 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{
+        close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
+        TransferChecked,
+    },
+};
+
+use crate::{EscrowError, Quote, Rfq};
+
+/// Pulls a quote and its tokens. The bond is returned once the RFQ has been accepted, cancelled
+/// or expired, and forfeited to the requester otherwise. The last quote out of a closed RFQ
+/// closes it.
+#[derive(Accounts)]
+pub struct WithdrawQuote<'info> {
+    #[account(mut)]
+    pub market_maker: Signer<'info>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = market_maker,
+        associated_token::token_program = token_program
+    )]
+    pub market_maker_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        address = quote.rfq,
+        has_one = requester @ EscrowError::Unauthorized,
+    )]
+    pub rfq: Account<'info, Rfq>,
+    /// CHECK: the RFQ's requester, which receives forfeited bonds and the RFQ's rent.
+    #[account(mut)]
+    pub requester: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = market_maker,
+        has_one = market_maker,
+        seeds = [b"quote", quote.rfq.as_ref(), market_maker.key().as_ref()],
+        bump = quote.bump
+    )]
+    pub quote: Account<'info, Quote>,
+    #[account(
+        mut,
+        associated_token::mint = mint_a,
+        associated_token::authority = quote,
+        associated_token::token_program = token_program
+    )]
+    pub vault: InterfaceAccount<'info, TokenAccount>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+impl<'info> WithdrawQuote<'info> {
+    pub fn settle_bond(&mut self) -> Result<()> {
+        if self.quote.bond == 0
+            || self.rfq.closed
+            || Clock::get()?.unix_timestamp >= self.rfq.expires_at
+        {
+            return Ok(());
+        }
+
+        self.quote.sub_lamports(self.quote.bond)?;
+        self.requester.add_lamports(self.quote.bond)?;
+        Ok(())
+    }
+
+    pub fn refund_and_close_vault(&mut self) -> Result<()> {
+        let signer_seeds: [&[&[u8]]; 1] = [&[
+            b"quote",
+            self.quote.rfq.as_ref(),
+            self.market_maker.to_account_info().key.as_ref(),
+            &[self.quote.bump],
+        ]];
+
+        let accounts = TransferChecked {
+            from: self.vault.to_account_info(),
+            mint: self.mint_a.to_account_info(),
+            to: self.market_maker_ata_a.to_account_info(),
+            authority: self.quote.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        transfer_checked(ctx, self.vault.amount, self.mint_a.decimals)?;
+
+        let accounts = CloseAccount {
+            account: self.vault.to_account_info(),
+            destination: self.market_maker.to_account_info(),
+            authority: self.quote.to_account_info(),
+        };
+
+        let ctx = CpiContext::new_with_signer(
+            self.token_program.to_account_info(),
+            accounts,
+            &signer_seeds,
+        );
+
+        close_account(ctx)
+    }
+
+    pub fn release_rfq(&mut self) -> Result<()> {
+        if self.rfq.release_quote() {
+            self.rfq.close(self.requester.to_account_info())?;
+        }
+        Ok(())
+    }
+}
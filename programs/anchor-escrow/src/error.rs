@@ -0,0 +1,145 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum EscrowError {
+    #[msg("Arithmetic overflow")]
+    Overflow,
+    #[msg("Seed does not match the maker counter")]
+    InvalidSeed,
+    #[msg("Instruction does not apply to this escrow mode")]
+    InvalidEscrowMode,
+    #[msg("Signer is not authorized for this action")]
+    Unauthorized,
+    #[msg("Basis points must not exceed 10000")]
+    InvalidBps,
+    #[msg("A fee account required by the config was not provided")]
+    MissingFeeAccount,
+    #[msg("Escrow cannot be refunded before its lock expires")]
+    RefundLocked,
+    #[msg("Escrow cannot be taken before its start time")]
+    NotStarted,
+    #[msg("Escrow has expired")]
+    Expired,
+    #[msg("Escrow is frozen")]
+    EscrowFrozen,
+    #[msg("Escrow duration is invalid or exceeds the configured maximum")]
+    InvalidDuration,
+    #[msg("Deposit is below the configured minimum")]
+    DepositTooSmall,
+    #[msg("Receive amount exceeds the configured maximum")]
+    ReceiveTooLarge,
+    #[msg("Fee rate denominator must be non-zero")]
+    InvalidFeeRate,
+    #[msg("Config change must be queued through the timelock")]
+    TimelockRequired,
+    #[msg("Timelock delay has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Admin instructions must be invoked through CPI")]
+    AdminCpiRequired,
+    #[msg("Escrow account version is not supported by this program")]
+    UnsupportedVersion,
+    #[msg("The escrowed mint cannot be swept")]
+    CannotSweepEscrowMint,
+    #[msg("Mints with a freeze authority are not allowed")]
+    FreezableMintNotAllowed,
+    #[msg("Mints with a permanent delegate require an explicit opt-in")]
+    PermanentDelegateNotAllowed,
+    #[msg("Token account is frozen, e.g. by a default-frozen mint; thaw it first")]
+    TokenAccountFrozen,
+    #[msg("Token account does not accept non-confidential credits")]
+    PublicCreditsDisabled,
+    #[msg("Non-transferable mints cannot be escrowed")]
+    NonTransferableMint,
+    #[msg("Mint is not wrapped SOL")]
+    NotNativeMint,
+    #[msg("Missing temporary wSOL account")]
+    MissingWsolAccount,
+    #[msg("Missing taker payment account")]
+    MissingPaymentAccount,
+    #[msg("Offer tree is full")]
+    OfferTreeFull,
+    #[msg("Merkle proof does not match a recent offer tree root")]
+    InvalidProof,
+    #[msg("Compressed offer has already been settled")]
+    OfferSettled,
+    #[msg("RFQ has expired")]
+    RfqExpired,
+    #[msg("Escrow prices do not cross")]
+    PricesDoNotCross,
+    #[msg("Payment option is not accepted by this escrow")]
+    InvalidPaymentOption,
+    #[msg("Fill amount is zero, exceeds the remaining deposit, or partial fills are disabled")]
+    InvalidFillAmount,
+    #[msg("Escrow has not expired")]
+    NotExpired,
+    #[msg("Maker has reached the creation limit for this epoch")]
+    CreationRateLimited,
+    #[msg("Price denominator must be non-zero")]
+    InvalidPrice,
+    #[msg("No tranche is due")]
+    NothingToClaim,
+    #[msg("Escrow has already been taken")]
+    AlreadyTaken,
+    #[msg("Escrows do not trade the same pair at the same price")]
+    PriceMismatch,
+    #[msg("Session key is invalid or has expired")]
+    InvalidSession,
+    #[msg("Escrow can't be taken through CPI")]
+    CpiNotAllowed,
+    #[msg("Flash take is not repaid later in the transaction")]
+    MissingRepayment,
+    #[msg("Wormhole accounts are missing or invalid")]
+    MissingWormholeAccount,
+    #[msg("Maker directory is full")]
+    DirectoryFull,
+    #[msg("Division by zero")]
+    DivideByZero,
+    #[msg("Vault does not match the escrow")]
+    InvalidVault,
+    #[msg("Price exceeds the taker's maximum")]
+    PriceTooHigh,
+    #[msg("Taker is not on the escrow's allowlist")]
+    TakerNotAllowed,
+    #[msg("Maker signature is missing or does not match the signed terms")]
+    InvalidMakerSignature,
+    #[msg("Signed terms are past their deadline")]
+    SignatureExpired,
+    #[msg("Payout account is missing or does not belong to its recipient")]
+    InvalidPayoutAccount,
+    #[msg("Take condition is not met or its account is missing")]
+    ConditionNotMet,
+    #[msg("Take condition must read between 1 and 8 bytes")]
+    InvalidCondition,
+    #[msg("Next scheduled fill is not due yet")]
+    TrancheNotDue,
+    #[msg("Swap router is not registered")]
+    RouterNotAllowed,
+    #[msg("Route does not pay enough mint_b into the taker's account")]
+    InvalidRouteOutput,
+    #[msg("Escrow requires its co-signer to sign the take")]
+    MissingCoSigner,
+    #[msg("Escrow requires the compliance signer to sign the take")]
+    MissingComplianceSignature,
+    #[msg("Taker credential is missing or invalid")]
+    InvalidCredential,
+    #[msg("Address is on the denylist")]
+    AddressDenied,
+    #[msg("Frontend fee is above the configured maximum")]
+    FrontendFeeTooHigh,
+    #[msg("Insurance claim amount is zero or exceeds the pool")]
+    InvalidClaimAmount,
+    #[msg("Stake amount is zero or exceeds the staked balance")]
+    InvalidStakeAmount,
+    #[msg("Mint is not the configured buyback token, or is the fee being spent")]
+    InvalidBuybackMint,
+    #[msg("Buyback spends more than the configured cap")]
+    BuybackLimitExceeded,
+    #[msg("Fee epoch account is not for the current epoch")]
+    StaleFeeEpoch,
+    #[msg("Config changed since this change was queued; cancel and queue it again")]
+    ConfigChanged,
+    #[msg("RFQ has been accepted or cancelled")]
+    RfqClosed,
+    #[msg("Escrow predates 32-byte seeds and can't be migrated; refund it with refund_legacy")]
+    LegacyEscrow,
+}
@@ -0,0 +1,128 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct MakeEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub deposit: u64,
+    pub receive: u64,
+    pub note: [u8; 64],
+}
+
+#[event]
+pub struct TakeEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub amount: u64,
+    pub receive: u64,
+    /// mint_b take fee charged, including any referral share.
+    pub fee: u64,
+    /// Protocol tokens paid by the taker in place of the mint_b fee; zero when paid in mint_b.
+    pub protocol_fee: u64,
+    pub referrer: Option<Pubkey>,
+    pub referral_fee: u64,
+    /// mint_b sent to the escrow's donation recipient.
+    pub donation: u64,
+    /// mint_b sent to the escrow's frontend.
+    pub frontend_fee: u64,
+    pub note: [u8; 64],
+}
+
+#[event]
+pub struct FreezeEvent {
+    pub escrow: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct EmergencyWithdrawQueuedEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub execute_after: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub amount: u64,
+}
+
+/// Emitted instead of a transfer when an escrow is closed with an empty vault, e.g. after a
+/// permanent delegate clawed the deposit back.
+#[event]
+pub struct VaultDrainedEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub expected: u64,
+}
+
+#[event]
+pub struct CompressedMakeEvent {
+    pub offer_tree: Pubkey,
+    pub index: u32,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount: u64,
+    pub receive: u64,
+}
+
+#[event]
+pub struct CompressedSettleEvent {
+    pub offer_tree: Pubkey,
+    pub index: u32,
+    /// None when the maker refunded.
+    pub taker: Option<Pubkey>,
+}
+
+#[event]
+pub struct QuoteEvent {
+    pub rfq: Pubkey,
+    pub quote: Pubkey,
+    pub market_maker: Pubkey,
+    pub receive: u64,
+    pub accepted: bool,
+}
+
+#[event]
+pub struct MatchEvent {
+    pub escrow_a: Pubkey,
+    pub escrow_b: Pubkey,
+    pub matcher: Pubkey,
+    /// Take fee on escrow_a's proceeds, in mint_y.
+    pub fee_a: u64,
+    /// Take fee on escrow_b's proceeds, in mint_x.
+    pub fee_b: u64,
+    /// Matcher's cut of the surplus returned to maker_a, in mint_x.
+    pub incentive_x: u64,
+    /// Matcher's cut of the surplus returned to maker_b, in mint_y.
+    pub incentive_y: u64,
+}
+
+#[event]
+pub struct BidEvent {
+    pub escrow: Pubkey,
+    pub bid: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    pub accepted: bool,
+}
+
+#[event]
+pub struct BuybackEvent {
+    pub fee_mint: Pubkey,
+    pub burn_mint: Pubkey,
+    pub burned: u64,
+}
+
+#[event]
+pub struct RefundEvent {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+}
@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+
+use anchor_spl::{
+    token::spl_token,
+    token_2022::{
+        self,
+        spl_token_2022::{
+            self, amount_to_ui_amount_string_trimmed,
+            extension::{
+                confidential_transfer::ConfidentialTransferAccount,
+                interest_bearing_mint::InterestBearingConfig, transfer_fee::TransferFeeConfig,
+                BaseStateWithExtensions, ExtensionType, StateWithExtensions,
+            },
+            state::{Account, Mint},
+        },
+    },
+};
+
+use crate::{EscrowError, EscrowParams};
+
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::ID || *mint == spl_token_2022::native_mint::ID
+}
+
+/// Token-2022 extensions initialized on `mint`; always empty for legacy token mints.
+pub fn mint_extensions(mint: &AccountInfo) -> Result<Vec<ExtensionType>> {
+    if *mint.owner != token_2022::ID {
+        return Ok(vec![]);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    Ok(state.get_extension_types()?)
+}
+
+/// UI representation of a raw `amount` of `mint` at `now`, including accrued interest for
+/// interest-bearing mints.
+pub fn ui_amount(mint: &AccountInfo, amount: u64, decimals: u8, now: i64) -> Result<String> {
+    if *mint.owner == token_2022::ID {
+        let data = mint.try_borrow_data()?;
+        let state = StateWithExtensions::<Mint>::unpack(&data)?;
+        if let Ok(config) = state.get_extension::<InterestBearingConfig>() {
+            return config
+                .amount_to_ui_amount(amount, decimals, now)
+                .ok_or(EscrowError::Overflow.into());
+        }
+    }
+    Ok(amount_to_ui_amount_string_trimmed(amount, decimals))
+}
+
+/// Token-2022 transfer fee withheld from a transfer of `amount` of `mint` during `epoch`; zero
+/// for mints without the extension.
+pub fn transfer_fee(mint: &AccountInfo, amount: u64, epoch: u64) -> Result<u64> {
+    if *mint.owner != token_2022::ID {
+        return Ok(0);
+    }
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<Mint>::unpack(&data)?;
+    match state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(epoch, amount)
+            .ok_or(EscrowError::Overflow.into()),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Whether `token_account` can be credited from a public balance. The escrow only ever moves
+/// the public leg of confidential transfer mints, so accounts that opted out of non-confidential
+/// credits can't receive settlement.
+pub fn accepts_public_credits(token_account: &AccountInfo) -> bool {
+    if *token_account.owner != token_2022::ID {
+        return true;
+    }
+    let Ok(data) = token_account.try_borrow_data() else {
+        return false;
+    };
+    let Ok(state) = StateWithExtensions::<Account>::unpack(&data) else {
+        return false;
+    };
+    match state.get_extension::<ConfidentialTransferAccount>() {
+        Ok(extension) => extension.allow_non_confidential_credits.into(),
+        Err(_) => true,
+    }
+}
+
+/// Rejects soulbound mints, which could be deposited but never settled.
+pub fn require_transferable(mint: &AccountInfo) -> Result<()> {
+    require!(
+        !mint_extensions(mint)?.contains(&ExtensionType::NonTransferable),
+        EscrowError::NonTransferableMint
+    );
+    Ok(())
+}
+
+/// Rejects mint_a extensions that would let the deposit leave the escrow's control.
+pub fn validate_deposit_mint(mint: &AccountInfo, params: &EscrowParams) -> Result<()> {
+    let extensions = mint_extensions(mint)?;
+    require!(
+        params.allow_permanent_delegate || !extensions.contains(&ExtensionType::PermanentDelegate),
+        EscrowError::PermanentDelegateNotAllowed
+    );
+    Ok(())
+}
@@ -0,0 +1,134 @@
+//! Bookkeeping shared by every instruction that fills an escrow, so statistics, fee records,
+//! points and rebates come out the same whichever take path a fill went through.
+
+use anchor_lang::prelude::*;
+
+use crate::{
+    EscrowError, FeeEpoch, Leaderboard, PairStats, Points, PointsRate, TakerRebate, Trade, TradeLog,
+};
+
+/// Adds `fee`, paid to the treasury by a fill, to the current epoch's bucket. The bucket may only
+/// be left out when there is no fee, so every treasury fee is counted.
+fn record_treasury_fee(fee_epoch: Option<&mut FeeEpoch>, fee: u64) -> Result<()> {
+    let Some(fee_epoch) = fee_epoch else {
+        require!(fee == 0, EscrowError::MissingFeeAccount);
+        return Ok(());
+    };
+    require_eq!(
+        fee_epoch.epoch,
+        Clock::get()?.epoch,
+        EscrowError::StaleFeeEpoch
+    );
+    fee_epoch.fees = fee_epoch
+        .fees
+        .checked_add(fee)
+        .ok_or(EscrowError::Overflow)?;
+    fee_epoch.fills = fee_epoch
+        .fills
+        .checked_add(1)
+        .ok_or(EscrowError::Overflow)?;
+    Ok(())
+}
+
+/// Credits the maker for the mint_a filled and the taker for the mint_b paid. Makers only earn
+/// points on fills, so a deposit that is refunded earns nothing.
+fn accrue_fill_points(
+    fill: &Fill,
+    maker_points: Option<&mut Points>,
+    maker_points_rate: Option<&PointsRate>,
+    taker_points: Option<&mut Points>,
+    taker_points_rate: Option<&PointsRate>,
+) -> Result<()> {
+    if let (Some(maker_points), Some(points_rate)) = (maker_points, maker_points_rate) {
+        maker_points.accrue(points_rate.make_points(fill.amount)?);
+    }
+    if let (Some(taker_points), Some(points_rate)) = (taker_points, taker_points_rate) {
+        taker_points.accrue(points_rate.take_points(fill.receive)?);
+    }
+    Ok(())
+}
+
+/// A settled fill, in raw units.
+pub struct Fill {
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    /// mint_a delivered to the taker.
+    pub amount: u64,
+    /// mint_b paid for it.
+    pub receive: u64,
+    /// The part of the mint_b take fee kept by the treasury.
+    pub treasury_fee: u64,
+    /// The part of the mint_b take fee accrued to the taker's rebate.
+    pub rebate: u64,
+}
+
+/// The optional accounts a fill is recorded in; each one that isn't passed is skipped.
+#[derive(Default)]
+pub struct FillRecords<'a, 'info> {
+    pub trade_log: Option<&'a AccountLoader<'info, TradeLog>>,
+    pub pair_stats: Option<&'a mut PairStats>,
+    /// Required when the fill paid the treasury a fee.
+    pub fee_epoch: Option<&'a mut FeeEpoch>,
+    pub maker_points: Option<&'a mut Points>,
+    /// Rate for mint_a, which the maker's points are credited at.
+    pub maker_points_rate: Option<&'a PointsRate>,
+    pub taker_points: Option<&'a mut Points>,
+    /// Rate for mint_b, which the taker's points are credited at.
+    pub points_rate: Option<&'a PointsRate>,
+    pub leaderboard: Option<&'a mut Leaderboard>,
+    pub taker_rebate: Option<&'a mut TakerRebate>,
+}
+
+impl FillRecords<'_, '_> {
+    pub fn record(self, fill: &Fill) -> Result<()> {
+        if let Some(trade_log) = self.trade_log {
+            trade_log.load_mut()?.push(Trade {
+                maker: fill.maker,
+                taker: fill.taker,
+                amount: fill.amount,
+                receive: fill.receive,
+                slot: Clock::get()?.slot,
+            });
+        }
+
+        record_treasury_fee(self.fee_epoch, fill.treasury_fee)?;
+
+        accrue_fill_points(
+            fill,
+            self.maker_points,
+            self.maker_points_rate,
+            self.taker_points,
+            self.points_rate,
+        )?;
+
+        if let Some(leaderboard) = self.leaderboard {
+            leaderboard.record(fill.maker, fill.receive);
+        }
+
+        if let Some(taker_rebate) = self.taker_rebate {
+            taker_rebate.volume = taker_rebate.volume.saturating_add(fill.receive);
+            taker_rebate.accrued = taker_rebate
+                .accrued
+                .checked_add(fill.rebate)
+                .ok_or(EscrowError::Overflow)?;
+        }
+
+        let Some(pair_stats) = self.pair_stats else {
+            return Ok(());
+        };
+
+        pair_stats.fills = pair_stats
+            .fills
+            .checked_add(1)
+            .ok_or(EscrowError::Overflow)?;
+        pair_stats.volume_a = pair_stats
+            .volume_a
+            .checked_add(fill.amount as u128)
+            .ok_or(EscrowError::Overflow)?;
+        pair_stats.volume_b = pair_stats
+            .volume_b
+            .checked_add(fill.receive as u128)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+}
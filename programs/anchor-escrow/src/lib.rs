@@ -6,23 +6,592 @@ use contexts::*;
 pub mod state;
 pub use state::*;
 
+pub mod error;
+pub use error::*;
+
+pub mod events;
+pub use events::*;
+
+pub mod extensions;
+pub use extensions::*;
+
+pub mod math;
+pub use math::*;
+
+pub mod fills;
+pub use fills::*;
+
+pub mod wormhole;
+pub use wormhole::*;
+
+pub mod router;
+pub use router::*;
+
 declare_id!("6BLPdL9narQPFQsqS7AXuRBRS4VoyKmHHzdwkgnLaAps");
 
+#[cfg(not(feature = "no-entrypoint"))]
+solana_security_txt::security_txt! {
+    name: "Anchor Escrow",
+    project_url: "https://github.com/deanmlittle/anchor-escrow-2024",
+    contacts: "link:https://github.com/deanmlittle/anchor-escrow-2024/security/advisories/new",
+    policy: "https://github.com/deanmlittle/anchor-escrow-2024/security/policy",
+    source_code: "https://github.com/deanmlittle/anchor-escrow-2024"
+}
+
 #[program]
 pub mod anchor_escrow {
     use super::*;
 
-    pub fn make(ctx: Context<Make>, seed: u64, deposit: u64, receive: u64) -> Result<()> {
+    pub fn make(
+        ctx: Context<Make>,
+        seed: [u8; 32],
+        deposit: u64,
+        receive: u64,
+        params: EscrowParams,
+    ) -> Result<()> {
+        ctx.accounts.pay_creation_fee()?;
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts
+            .save_escrow(seed, deposit, receive, params, &ctx.bumps)
+    }
+
+    pub fn clone_escrow(ctx: Context<CloneEscrow>, seed: [u8; 32], deposit: u64) -> Result<()> {
+        ctx.accounts.pay_creation_fee()?;
         ctx.accounts.deposit(deposit)?;
-        ctx.accounts.save_escrow(seed, receive, &ctx.bumps)
+        ctx.accounts.save_escrow(seed, deposit, &ctx.bumps)
+    }
+
+    pub fn create_template(
+        ctx: Context<CreateTemplate>,
+        id: u32,
+        params: TemplateParams,
+    ) -> Result<()> {
+        ctx.accounts.create_template(id, params, &ctx.bumps)
+    }
+
+    pub fn close_template(_ctx: Context<CloseTemplate>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn make_from_template(
+        ctx: Context<MakeFromTemplate>,
+        seed: [u8; 32],
+        deposit: u64,
+    ) -> Result<()> {
+        ctx.accounts.pay_creation_fee()?;
+        ctx.accounts.deposit(deposit)?;
+        ctx.accounts.save_escrow(seed, deposit, &ctx.bumps)
     }
 
     pub fn refund(ctx: Context<Refund>) -> Result<()> {
-        ctx.accounts.refund_and_close_vault()
+        ctx.accounts.check_unlocked()?;
+        ctx.accounts.memo()?;
+        ctx.accounts.pay_cancellation_fee()?;
+        ctx.accounts.refund_and_close_vault()?;
+        ctx.accounts.unlist()
+    }
+
+    pub fn create_session(
+        ctx: Context<CreateSession>,
+        signer: Pubkey,
+        expires_at: i64,
+        allowance: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_session(signer, expires_at, allowance, &ctx.bumps)
+    }
+
+    pub fn revoke_session(ctx: Context<RevokeSession>) -> Result<()> {
+        ctx.accounts.revoke_session()
+    }
+
+    pub fn register_keeper(ctx: Context<RegisterKeeper>) -> Result<()> {
+        ctx.accounts.register_keeper(&ctx.bumps)
+    }
+
+    pub fn claim_keeper_rewards(ctx: Context<ClaimKeeperRewards>) -> Result<()> {
+        ctx.accounts.claim_keeper_rewards()
     }
 
-    pub fn take(ctx: Context<Take>) -> Result<()> {
+    pub fn auto_refund(ctx: Context<AutoRefund>) -> Result<()> {
+        ctx.accounts.refund_and_close_vault()?;
+        ctx.accounts.unlist()?;
+        ctx.accounts.pay_tip()
+    }
+
+    pub fn take<'info>(
+        ctx: Context<'_, '_, '_, 'info, Take<'info>>,
+        _payment_index: u8,
+    ) -> Result<()> {
+        ctx.accounts.check_active()?;
+        if ctx.accounts.vault.amount > 0 {
+            let (split_accounts, condition_account, remaining_accounts) =
+                ctx.accounts.split_remaining(ctx.remaining_accounts)?;
+            ctx.accounts.check_condition(condition_account)?;
+            ctx.accounts.pay_protocol_fee()?;
+            ctx.accounts.wrap_for_taker()?;
+            ctx.accounts.deposit(split_accounts)?;
+            ctx.accounts.relist(&ctx.bumps)?;
+            ctx.accounts.close_taker_wsol()?;
+            ctx.accounts.unwrap_for_maker()?;
+            ctx.accounts.record_fill()?;
+            ctx.accounts.memo()?;
+            ctx.accounts.post_settlement(remaining_accounts)?;
+        }
+        ctx.accounts.withdraw_and_close_vault()
+    }
+
+    pub fn claim_stream(ctx: Context<ClaimStream>) -> Result<()> {
+        ctx.accounts.claim_stream()
+    }
+
+    pub fn flash_take(ctx: Context<FlashTake>) -> Result<()> {
+        ctx.accounts.check_active()?;
+        ctx.accounts.check_repayment()?;
+        ctx.accounts.withdraw()
+    }
+
+    pub fn flash_repay(ctx: Context<FlashRepay>) -> Result<()> {
         ctx.accounts.deposit()?;
+        ctx.accounts.record_fill()?;
+        ctx.accounts.close_vault()?;
+        ctx.accounts.unlist()
+    }
+
+    pub fn take_partial(ctx: Context<TakePartial>, fill: u64, max_in: u64) -> Result<()> {
+        ctx.accounts.check_active()?;
+        let fill = ctx.accounts.sweep_dust(fill);
+        ctx.accounts.check_schedule(fill)?;
+        let price = ctx.accounts.capped_price(fill, max_in)?;
+        ctx.accounts.deposit(price)?;
+        ctx.accounts.withdraw(fill, price)
+    }
+
+    pub fn take_exact_out(ctx: Context<TakePartial>, amount_out: u64, max_in: u64) -> Result<()> {
+        ctx.accounts.check_active()?;
+        let price = ctx.accounts.exact_out_price(amount_out, max_in)?;
+        ctx.accounts.check_schedule(amount_out)?;
+        ctx.accounts.deposit(price)?;
+        ctx.accounts.withdraw(amount_out, price)
+    }
+
+    pub fn take_with_route<'info>(
+        ctx: Context<'_, '_, '_, 'info, TakeWithRoute<'info>>,
+        route_data: Vec<u8>,
+    ) -> Result<()> {
+        ctx.accounts.check_active()?;
+        ctx.accounts.swap(route_data, ctx.remaining_accounts)?;
+        let fee = ctx.accounts.deposit()?;
+        ctx.accounts.record_fill()?;
+        ctx.accounts.withdraw_and_close_vault(fee)?;
+        ctx.accounts.unlist()
+    }
+
+    pub fn match_orders(ctx: Context<MatchOrders>) -> Result<()> {
+        ctx.accounts.check_crossing()?;
+        ctx.accounts.settle()?;
+        ctx.accounts.record_fills()?;
+        ctx.accounts.unlist()?;
+        ctx.accounts.record_crank()
+    }
+
+    pub fn split_escrow(ctx: Context<SplitEscrow>, seed: [u8; 32], amount: u64) -> Result<()> {
+        ctx.accounts.split(seed, amount, &ctx.bumps)?;
+        ctx.accounts.move_deposit()
+    }
+
+    pub fn merge_escrows(ctx: Context<MergeEscrows>) -> Result<()> {
+        ctx.accounts.merge()?;
+        ctx.accounts.move_and_close_vault()?;
+        ctx.accounts.unlist()
+    }
+
+    pub fn update_escrow(ctx: Context<UpdateEscrow>, receive: u64) -> Result<()> {
+        ctx.accounts.update_escrow(receive)
+    }
+
+    pub fn extend_escrow(ctx: Context<ExtendEscrow>, expires_at: i64) -> Result<()> {
+        ctx.accounts.extend_escrow(expires_at)
+    }
+
+    pub fn make_subscription(
+        ctx: Context<MakeSubscription>,
+        seed: [u8; 32],
+        amount_per_period: u64,
+        period: i64,
+        starts_at: i64,
+        periods: u32,
+    ) -> Result<()> {
+        ctx.accounts.save_subscription(
+            seed,
+            amount_per_period,
+            period,
+            starts_at,
+            periods,
+            &ctx.bumps,
+        )?;
+        ctx.accounts.deposit()
+    }
+
+    pub fn claim_period(ctx: Context<ClaimPeriod>) -> Result<()> {
+        ctx.accounts.claim_period()
+    }
+
+    pub fn cancel_subscription(ctx: Context<CancelSubscription>) -> Result<()> {
+        ctx.accounts.settle_and_close_vault()
+    }
+
+    pub fn make_delegated(
+        ctx: Context<MakeDelegated>,
+        seed: [u8; 32],
+        deposit: u64,
+        receive: u64,
+        params: EscrowParams,
+    ) -> Result<()> {
+        ctx.accounts.pay_creation_fee()?;
+        ctx.accounts.approve(deposit)?;
+        ctx.accounts
+            .save_escrow(seed, deposit, receive, params, &ctx.bumps)
+    }
+
+    pub fn make_with_signature(ctx: Context<MakeWithSignature>, terms: SignedTerms) -> Result<()> {
+        ctx.accounts.check_signature(&terms)?;
+        ctx.accounts.pay_creation_fee()?;
+        ctx.accounts.deposit(terms.deposit, &ctx.bumps)?;
+        ctx.accounts.save_escrow(&terms, &ctx.bumps)
+    }
+
+    pub fn cancel_delegated(ctx: Context<CancelDelegated>) -> Result<()> {
+        ctx.accounts.check_unlocked()?;
+        ctx.accounts.pay_cancellation_fee()?;
+        ctx.accounts.revoke()?;
+        ctx.accounts.unlist()
+    }
+
+    pub fn take_delegated(ctx: Context<TakeDelegated>) -> Result<()> {
+        ctx.accounts.check_active()?;
+        ctx.accounts.deposit()?;
+        ctx.accounts.record_fill()?;
+        ctx.accounts.withdraw()?;
+        ctx.accounts.unlist()
+    }
+
+    pub fn make_stake(ctx: Context<MakeStake>, seed: [u8; 32], receive: u64) -> Result<()> {
+        ctx.accounts.assign_authorities()?;
+        ctx.accounts.save_stake_escrow(seed, receive, &ctx.bumps)
+    }
+
+    pub fn take_stake(ctx: Context<TakeStake>) -> Result<()> {
+        ctx.accounts.deposit()?;
+        ctx.accounts.transfer_authorities()
+    }
+
+    pub fn refund_stake(ctx: Context<RefundStake>) -> Result<()> {
+        ctx.accounts.return_authorities()
+    }
+
+    pub fn init_offer_tree(ctx: Context<InitOfferTree>, id: u32) -> Result<()> {
+        ctx.accounts.init_offer_tree(id, &ctx.bumps)
+    }
+
+    pub fn make_compressed(ctx: Context<MakeCompressed>, amount: u64, receive: u64) -> Result<()> {
+        ctx.accounts.validate(amount, receive, &ctx.bumps)?;
+        ctx.accounts.pay_creation_fee()?;
+        let received = ctx.accounts.deposit(amount)?;
+        ctx.accounts.append_offer(received, receive)
+    }
+
+    pub fn take_compressed(
+        ctx: Context<TakeCompressed>,
+        index: u32,
+        offer: CompressedOffer,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.settle(index, &offer, &proof)?;
+        ctx.accounts.deposit(&offer)?;
+        ctx.accounts.withdraw(&offer)
+    }
+
+    pub fn refund_compressed(
+        ctx: Context<RefundCompressed>,
+        index: u32,
+        offer: CompressedOffer,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        ctx.accounts.settle(index, &offer, &proof)?;
+        ctx.accounts.refund(&offer)
+    }
+
+    pub fn create_rfq(
+        ctx: Context<CreateRfq>,
+        seed: [u8; 32],
+        amount: u64,
+        bond: u64,
+        expires_at: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_rfq(seed, amount, bond, expires_at, &ctx.bumps)
+    }
+
+    pub fn cancel_rfq(ctx: Context<CancelRfq>) -> Result<()> {
+        ctx.accounts.cancel_rfq()
+    }
+
+    pub fn submit_quote(ctx: Context<SubmitQuote>, receive: u64) -> Result<()> {
+        ctx.accounts.submit_quote(receive, &ctx.bumps)?;
+        ctx.accounts.lock_bond()?;
+        ctx.accounts.deposit()
+    }
+
+    pub fn accept_quote(ctx: Context<AcceptQuote>) -> Result<()> {
+        ctx.accounts.deposit()?;
+        ctx.accounts.withdraw_and_close_vault()?;
+        ctx.accounts.close_rfq()
+    }
+
+    pub fn withdraw_quote(ctx: Context<WithdrawQuote>) -> Result<()> {
+        ctx.accounts.settle_bond()?;
+        ctx.accounts.refund_and_close_vault()?;
+        ctx.accounts.release_rfq()
+    }
+
+    pub fn place_bid(ctx: Context<PlaceBid>, amount: u64, locked_until: i64) -> Result<()> {
+        ctx.accounts.place_bid(amount, locked_until, &ctx.bumps)?;
+        ctx.accounts.deposit()
+    }
+
+    pub fn accept_bid(ctx: Context<AcceptBid>) -> Result<()> {
+        ctx.accounts.check_active()?;
+        ctx.accounts.deposit_and_close_bid_vault()?;
+        ctx.accounts.record_fill()?;
         ctx.accounts.withdraw_and_close_vault()
     }
+
+    pub fn withdraw_bid(ctx: Context<WithdrawBid>) -> Result<()> {
+        ctx.accounts.check_withdrawable()?;
+        ctx.accounts.refund_and_close_vault()
+    }
+
+    pub fn quote_ui_amounts(ctx: Context<QuoteUiAmounts>) -> Result<UiQuote> {
+        ctx.accounts.quote_ui_amounts()
+    }
+
+    pub fn quote_with_fees(ctx: Context<QuoteWithFees>, fill: u64) -> Result<FeeQuote> {
+        ctx.accounts.quote_with_fees(fill)
+    }
+
+    pub fn initialize_config(ctx: Context<InitializeConfig>, params: ConfigParams) -> Result<()> {
+        ctx.accounts.initialize_config(params, &ctx.bumps)
+    }
+
+    pub fn update_config(ctx: Context<UpdateConfig>, params: ConfigParams) -> Result<()> {
+        ctx.accounts.update_config(params)
+    }
+
+    pub fn queue_config_change(
+        ctx: Context<QueueConfigChange>,
+        params: ConfigParams,
+    ) -> Result<()> {
+        ctx.accounts.queue_config_change(params, &ctx.bumps)
+    }
+
+    pub fn execute_config_change(ctx: Context<ExecuteConfigChange>) -> Result<()> {
+        ctx.accounts.execute_config_change()
+    }
+
+    pub fn cancel_config_change(_ctx: Context<CancelConfigChange>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn nominate_admin(ctx: Context<NominateAdmin>, pending_admin: Pubkey) -> Result<()> {
+        ctx.accounts.nominate_admin(pending_admin)
+    }
+
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        ctx.accounts.accept_admin()
+    }
+
+    pub fn freeze_escrow(ctx: Context<FreezeEscrow>) -> Result<()> {
+        ctx.accounts.set_frozen(true)
+    }
+
+    pub fn unfreeze_escrow(ctx: Context<FreezeEscrow>) -> Result<()> {
+        ctx.accounts.set_frozen(false)
+    }
+
+    pub fn queue_emergency_withdraw(ctx: Context<QueueEmergencyWithdraw>) -> Result<()> {
+        ctx.accounts.queue_emergency_withdraw(&ctx.bumps)
+    }
+
+    pub fn cancel_emergency_withdraw(_ctx: Context<CancelEmergencyWithdraw>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn emergency_withdraw(ctx: Context<EmergencyWithdraw>) -> Result<()> {
+        ctx.accounts.emergency_withdraw()?;
+        ctx.accounts.unlist()
+    }
+
+    pub fn set_fee_override(ctx: Context<SetFeeOverride>, fee_bps: u16) -> Result<()> {
+        ctx.accounts.set_fee_override(fee_bps, &ctx.bumps)
+    }
+
+    pub fn remove_fee_override(_ctx: Context<RemoveFeeOverride>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn register_router(ctx: Context<RegisterRouter>) -> Result<()> {
+        ctx.accounts.register_router(&ctx.bumps)
+    }
+
+    pub fn remove_router(_ctx: Context<RemoveRouter>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_buyback_config(ctx: Context<SetBuybackConfig>, max_amount_in: u64) -> Result<()> {
+        ctx.accounts.set_buyback_config(max_amount_in, &ctx.bumps)
+    }
+
+    pub fn buyback_and_burn<'info>(
+        ctx: Context<'_, '_, '_, 'info, BuybackAndBurn<'info>>,
+        amount_in: u64,
+        min_out: u64,
+        route_data: Vec<u8>,
+    ) -> Result<()> {
+        let bought = ctx
+            .accounts
+            .swap(amount_in, min_out, route_data, ctx.remaining_accounts)?;
+        ctx.accounts.burn(bought)
+    }
+
+    pub fn deny_address(ctx: Context<DenyAddress>, address: Pubkey) -> Result<()> {
+        ctx.accounts.deny_address(address, &ctx.bumps)
+    }
+
+    pub fn allow_address(_ctx: Context<AllowAddress>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn set_protocol_fee_rate(
+        ctx: Context<SetProtocolFeeRate>,
+        numerator: u64,
+        denominator: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_protocol_fee_rate(numerator, denominator, &ctx.bumps)
+    }
+
+    pub fn register_affiliate(ctx: Context<RegisterAffiliate>) -> Result<()> {
+        ctx.accounts.register_affiliate(&ctx.bumps)
+    }
+
+    pub fn claim_affiliate_fees(ctx: Context<ClaimAffiliateFees>) -> Result<()> {
+        ctx.accounts.claim_affiliate_fees()
+    }
+
+    pub fn init_insurance_pool(ctx: Context<InitInsurancePool>) -> Result<()> {
+        ctx.accounts.init_insurance_pool(&ctx.bumps)
+    }
+
+    pub fn file_insurance_claim(
+        ctx: Context<FileInsuranceClaim>,
+        escrow: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .file_insurance_claim(escrow, amount, &ctx.bumps)
+    }
+
+    pub fn approve_insurance_claim(ctx: Context<ApproveInsuranceClaim>) -> Result<()> {
+        ctx.accounts.approve_insurance_claim()
+    }
+
+    pub fn reject_insurance_claim(_ctx: Context<RejectInsuranceClaim>) -> Result<()> {
+        Ok(())
+    }
+
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        ctx.accounts.stake(amount, &ctx.bumps)
+    }
+
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        ctx.accounts.unstake(amount)
+    }
+
+    pub fn set_rebate_schedule(
+        ctx: Context<SetRebateSchedule>,
+        tiers: [RebateTier; MAX_REBATE_TIERS],
+    ) -> Result<()> {
+        ctx.accounts.set_rebate_schedule(tiers, &ctx.bumps)
+    }
+
+    pub fn init_taker_rebate(ctx: Context<InitTakerRebate>) -> Result<()> {
+        ctx.accounts.init_taker_rebate(&ctx.bumps)
+    }
+
+    pub fn claim_rebate(ctx: Context<ClaimRebate>) -> Result<()> {
+        ctx.accounts.claim_rebate()
+    }
+
+    pub fn migrate_escrow(ctx: Context<MigrateEscrow>) -> Result<()> {
+        ctx.accounts.migrate_escrow()
+    }
+
+    pub fn refund_legacy(ctx: Context<RefundLegacy>) -> Result<()> {
+        ctx.accounts.refund_and_close()
+    }
+
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        ctx.accounts.migrate_config()
+    }
+
+    pub fn sweep(ctx: Context<Sweep>) -> Result<()> {
+        ctx.accounts.sweep()
+    }
+
+    pub fn recover_lamports(ctx: Context<RecoverLamports>) -> Result<()> {
+        ctx.accounts.recover_escrow_lamports()?;
+        ctx.accounts.recover_vault_lamports()
+    }
+
+    pub fn init_maker_directory(ctx: Context<InitMakerDirectory>) -> Result<()> {
+        ctx.accounts.init_maker_directory(&ctx.bumps)
+    }
+
+    pub fn unlist_escrow(ctx: Context<UnlistEscrow>, seed: [u8; 32]) -> Result<()> {
+        ctx.accounts.unlist_escrow(seed)
+    }
+
+    pub fn init_maker_counter(ctx: Context<InitMakerCounter>) -> Result<()> {
+        ctx.accounts.init_maker_counter(&ctx.bumps)
+    }
+
+    pub fn init_trade_log(ctx: Context<InitTradeLog>) -> Result<()> {
+        ctx.accounts.init_trade_log(&ctx.bumps)
+    }
+
+    pub fn init_pair_stats(ctx: Context<InitPairStats>) -> Result<()> {
+        ctx.accounts.init_pair_stats(&ctx.bumps)
+    }
+
+    pub fn init_fee_epoch(ctx: Context<InitFeeEpoch>, epoch: u64) -> Result<()> {
+        ctx.accounts.init_fee_epoch(epoch, &ctx.bumps)
+    }
+
+    pub fn init_leaderboard(ctx: Context<InitLeaderboard>) -> Result<()> {
+        ctx.accounts.init_leaderboard(&ctx.bumps)
+    }
+
+    pub fn init_points(ctx: Context<InitPoints>) -> Result<()> {
+        ctx.accounts.init_points(&ctx.bumps)
+    }
+
+    pub fn set_points_rate(
+        ctx: Context<SetPointsRate>,
+        make_multiplier_bps: u64,
+        take_multiplier_bps: u64,
+    ) -> Result<()> {
+        ctx.accounts
+            .set_points_rate(make_multiplier_bps, take_multiplier_bps, &ctx.bumps)
+    }
 }
@@ -0,0 +1,89 @@
+//! Amount math shared by pricing, fees and fills. Products are taken in u128 so they can't
+//! overflow, and every division states which way it rounds.
+
+use anchor_lang::prelude::*;
+
+use crate::EscrowError;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+#[derive(Clone, Copy)]
+pub enum Rounding {
+    /// Towards zero; used for amounts paid out of the program, such as fees.
+    Down,
+    /// Away from zero; used for prices so that rounding favours the maker.
+    Up,
+}
+
+/// `amount * numerator / denominator`, failing if the result doesn't fit in a u64.
+pub fn mul_div(amount: u64, numerator: u64, denominator: u64, rounding: Rounding) -> Result<u64> {
+    require!(denominator != 0, EscrowError::DivideByZero);
+    let product = (amount as u128) * (numerator as u128);
+    let result = match rounding {
+        Rounding::Down => product / denominator as u128,
+        Rounding::Up => product.div_ceil(denominator as u128),
+    };
+    u64::try_from(result).map_err(|_| EscrowError::Overflow.into())
+}
+
+/// `bps` basis points of `amount`, rounded down.
+pub fn bps_of(amount: u64, bps: u16) -> Result<u64> {
+    mul_div(amount, bps as u64, BPS_DENOMINATOR, Rounding::Down)
+}
+
+/// Whether `a_numerator / a_denominator == b_numerator / b_denominator`, compared exactly.
+pub fn same_ratio(
+    a_numerator: u64,
+    a_denominator: u64,
+    b_numerator: u64,
+    b_denominator: u64,
+) -> bool {
+    (a_numerator as u128) * (b_denominator as u128)
+        == (b_numerator as u128) * (a_denominator as u128)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mul_div_rounds_as_asked() {
+        assert_eq!(mul_div(10, 1, 3, Rounding::Down).unwrap(), 3);
+        assert_eq!(mul_div(10, 1, 3, Rounding::Up).unwrap(), 4);
+        assert_eq!(mul_div(9, 1, 3, Rounding::Up).unwrap(), 3);
+    }
+
+    #[test]
+    fn mul_div_takes_products_beyond_u64() {
+        assert_eq!(
+            mul_div(u64::MAX, u64::MAX, u64::MAX, Rounding::Down).unwrap(),
+            u64::MAX
+        );
+    }
+
+    #[test]
+    fn mul_div_fails_on_zero_denominator_and_overflow() {
+        assert_eq!(
+            mul_div(1, 1, 0, Rounding::Down).unwrap_err(),
+            EscrowError::DivideByZero.into()
+        );
+        assert_eq!(
+            mul_div(u64::MAX, 2, 1, Rounding::Down).unwrap_err(),
+            EscrowError::Overflow.into()
+        );
+    }
+
+    #[test]
+    fn bps_of_rounds_down() {
+        assert_eq!(bps_of(10_000, 30).unwrap(), 30);
+        assert_eq!(bps_of(333, 30).unwrap(), 0);
+        assert_eq!(bps_of(u64::MAX, 10_000).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn same_ratio_compares_exactly() {
+        assert!(same_ratio(1, 3, 2, 6));
+        assert!(!same_ratio(1, 3, 333_333, 1_000_000));
+        assert!(same_ratio(u64::MAX, u64::MAX, 1, 1));
+    }
+}
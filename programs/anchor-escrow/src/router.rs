@@ -0,0 +1,34 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke,
+    },
+};
+
+/// Invokes `router` with the taker-built swap instruction `data` over `accounts`, passed as
+/// remaining accounts in the order the router expects. No program signature is added, so the
+/// swap can only move funds the transaction's signers control.
+pub fn invoke_route<'info>(
+    router: &AccountInfo<'info>,
+    data: Vec<u8>,
+    accounts: &[AccountInfo<'info>],
+) -> Result<()> {
+    let instruction = Instruction {
+        program_id: *router.key,
+        accounts: accounts
+            .iter()
+            .map(|account| AccountMeta {
+                pubkey: *account.key,
+                is_signer: account.is_signer,
+                is_writable: account.is_writable,
+            })
+            .collect(),
+        data,
+    };
+
+    let mut account_infos = accounts.to_vec();
+    account_infos.push(router.clone());
+    invoke(&instruction, &account_infos)?;
+    Ok(())
+}
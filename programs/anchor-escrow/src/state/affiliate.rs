@@ -0,0 +1,12 @@
+use anchor_lang::prelude::*;
+
+/// Referral fees in `mint` earned by `owner`, held in the affiliate's own token account until claimed.
+#[account]
+#[derive(InitSpace)]
+pub struct Affiliate {
+    pub owner: Pubkey,
+    pub mint: Pubkey,
+    pub accrued: u64,
+    pub referrals: u64,
+    pub bump: u8,
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// A bidder's offer for a sealed-bid escrow's whole deposit. The bid's mint_b sits in the bid's
+/// vault until the maker accepts it or the bidder withdraws it.
+#[account]
+#[derive(InitSpace)]
+pub struct Bid {
+    pub escrow: Pubkey,
+    pub bidder: Pubkey,
+    pub amount: u64,
+    /// Unix timestamp before which the bidder cannot withdraw while the escrow is open.
+    pub locked_until: i64,
+    pub bump: u8,
+}
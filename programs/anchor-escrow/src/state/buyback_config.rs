@@ -0,0 +1,11 @@
+use anchor_lang::prelude::*;
+
+/// Admin-set parameters for `buyback_and_burn`: the token bought with treasury fees and burned,
+/// and the most fee tokens a single buyback may spend. A zero cap disables buybacks.
+#[account]
+#[derive(InitSpace)]
+pub struct BuybackConfig {
+    pub mint: Pubkey,
+    pub max_amount_in: u64,
+    pub bump: u8,
+}
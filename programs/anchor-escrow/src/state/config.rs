@@ -0,0 +1,417 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hash,
+        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+        sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+    },
+};
+
+use anchor_spl::token_interface::Mint;
+
+use crate::{bps_of, EscrowError, EscrowParams, FeeOverride, FeeStake};
+
+pub const MAX_CPI_CALLERS: usize = 4;
+
+pub const MAX_STAKE_TIERS: usize = 2;
+
+/// Takers with at least `min_stake` protocol tokens staked pay `discount_bps` less take fee.
+/// Unused tiers are all zero.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub struct StakeTier {
+    pub min_stake: u64,
+    pub discount_bps: u16,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    /// Admin nominated by `nominate_admin`, pending their acceptance; the default key if none.
+    pub pending_admin: Pubkey,
+    pub treasury: Pubkey,
+    /// Flat lamport fee charged to the maker on every make.
+    pub creation_fee: u64,
+    /// Flat lamport fee charged to the maker on refund.
+    pub cancellation_fee: u64,
+    /// Share of the refunded deposit, in basis points, sent to the treasury on refund.
+    pub cancellation_fee_bps: u16,
+    /// Longest lifetime, in seconds, an escrow may be created with; zero for no cap.
+    pub max_duration: i64,
+    /// Smallest deposit, in raw mint_a units, accepted by make.
+    pub min_deposit: u64,
+    /// Largest receive, in raw mint_b units, accepted by make; zero for no cap.
+    pub max_receive: u64,
+    /// Protocol fee, in basis points, taken from the mint_b payment on every take.
+    pub take_fee_bps: u16,
+    /// Mint in which takers may pay the protocol fee instead of mint_b; the default key disables it.
+    pub protocol_mint: Pubkey,
+    /// Discount, in basis points, applied to fees paid in `protocol_mint`.
+    pub protocol_fee_discount_bps: u16,
+    /// Burn fees paid in `protocol_mint` instead of sending them to the treasury.
+    pub burn_protocol_fee: bool,
+    /// Share of the mint_b take fee, in basis points, paid to the referrer when one is supplied.
+    pub referral_share_bps: u16,
+    /// Delay, in seconds, that user-unfavourable changes must wait in `PendingConfigChange`.
+    pub timelock: i64,
+    /// Only accept admin instructions invoked through CPI, e.g. signed by a governance PDA.
+    pub admin_cpi_only: bool,
+    /// Refuse to make escrows over mints with a freeze authority, which could block settlement.
+    pub reject_freezable_mints: bool,
+    /// Share of each side's crossing surplus, in basis points, paid to whoever matches two escrows.
+    pub match_incentive_bps: u16,
+    /// Lamports from the escrow's rent paid to whoever executes `auto_refund` after expiry.
+    pub auto_refund_tip: u64,
+    /// Most escrows a single maker may create per epoch; zero for no cap.
+    pub max_creations_per_epoch: u32,
+    /// Smallest deposit, in whole mint_a tokens scaled by the mint's decimals, accepted by make.
+    pub min_deposit_tokens: u64,
+    /// Partial fills that would leave fewer raw mint_a units than this take the whole remainder.
+    pub dust_threshold: u64,
+    /// Programs allowed to CPI into take; unused slots hold the default key and an all-default list allows any.
+    pub cpi_allowlist: [Pubkey; MAX_CPI_CALLERS],
+    pub bump: u8,
+    /// Key that co-signs takes of escrows with `require_compliance` after its off-chain checks;
+    /// the default key leaves such escrows untakeable.
+    pub compliance_signer: Pubkey,
+    /// Highest frontend fee, in basis points, an escrow may be made with.
+    pub max_frontend_fee_bps: u16,
+    /// Premium, in basis points of the price, paid on top by takers who insure their take.
+    pub insurance_premium_bps: u16,
+    /// Take fee discounts for takers staking `protocol_mint`; the deepest tier reached applies.
+    pub stake_tiers: [StakeTier; MAX_STAKE_TIERS],
+    /// Zeroed space for new fields, which are carved from its front so adding them needs no
+    /// migration. Only when it runs low does the account grow, through `migrate_config`, to
+    /// restore it.
+    pub reserved: [u8; 64],
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace)]
+pub struct ConfigParams {
+    pub treasury: Pubkey,
+    pub creation_fee: u64,
+    pub cancellation_fee: u64,
+    pub cancellation_fee_bps: u16,
+    pub max_duration: i64,
+    pub min_deposit: u64,
+    pub max_receive: u64,
+    pub take_fee_bps: u16,
+    pub protocol_mint: Pubkey,
+    pub protocol_fee_discount_bps: u16,
+    pub burn_protocol_fee: bool,
+    pub referral_share_bps: u16,
+    pub timelock: i64,
+    pub admin_cpi_only: bool,
+    pub reject_freezable_mints: bool,
+    pub match_incentive_bps: u16,
+    pub auto_refund_tip: u64,
+    pub max_creations_per_epoch: u32,
+    pub min_deposit_tokens: u64,
+    pub dust_threshold: u64,
+    pub cpi_allowlist: [Pubkey; MAX_CPI_CALLERS],
+    pub compliance_signer: Pubkey,
+    pub max_frontend_fee_bps: u16,
+    pub insurance_premium_bps: u16,
+    pub stake_tiers: [StakeTier; MAX_STAKE_TIERS],
+}
+
+impl ConfigParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.cancellation_fee_bps <= 10_000, EscrowError::InvalidBps);
+        require!(self.take_fee_bps <= 10_000, EscrowError::InvalidBps);
+        require!(
+            self.protocol_fee_discount_bps <= 10_000,
+            EscrowError::InvalidBps
+        );
+        require!(self.referral_share_bps <= 10_000, EscrowError::InvalidBps);
+        require!(self.match_incentive_bps <= 10_000, EscrowError::InvalidBps);
+        require!(self.max_frontend_fee_bps <= 10_000, EscrowError::InvalidBps);
+        require!(
+            self.insurance_premium_bps <= 10_000,
+            EscrowError::InvalidBps
+        );
+        for tier in &self.stake_tiers {
+            require!(tier.discount_bps <= 10_000, EscrowError::InvalidBps);
+        }
+        require!(self.max_duration >= 0, EscrowError::InvalidDuration);
+        require!(self.timelock >= 0, EscrowError::InvalidDuration);
+        Ok(())
+    }
+}
+
+impl Config {
+    pub fn params(&self) -> ConfigParams {
+        ConfigParams {
+            treasury: self.treasury,
+            creation_fee: self.creation_fee,
+            cancellation_fee: self.cancellation_fee,
+            cancellation_fee_bps: self.cancellation_fee_bps,
+            max_duration: self.max_duration,
+            min_deposit: self.min_deposit,
+            max_receive: self.max_receive,
+            take_fee_bps: self.take_fee_bps,
+            protocol_mint: self.protocol_mint,
+            protocol_fee_discount_bps: self.protocol_fee_discount_bps,
+            burn_protocol_fee: self.burn_protocol_fee,
+            referral_share_bps: self.referral_share_bps,
+            timelock: self.timelock,
+            admin_cpi_only: self.admin_cpi_only,
+            reject_freezable_mints: self.reject_freezable_mints,
+            match_incentive_bps: self.match_incentive_bps,
+            auto_refund_tip: self.auto_refund_tip,
+            max_creations_per_epoch: self.max_creations_per_epoch,
+            min_deposit_tokens: self.min_deposit_tokens,
+            dust_threshold: self.dust_threshold,
+            cpi_allowlist: self.cpi_allowlist,
+            compliance_signer: self.compliance_signer,
+            max_frontend_fee_bps: self.max_frontend_fee_bps,
+            insurance_premium_bps: self.insurance_premium_bps,
+            stake_tiers: self.stake_tiers,
+        }
+    }
+
+    /// Hash of the current settings, so a queued change can tell whether they moved since.
+    pub fn params_hash(&self) -> Result<[u8; 32]> {
+        Ok(hash(&self.params().try_to_vec()?).to_bytes())
+    }
+
+    pub fn apply(&mut self, params: &ConfigParams) {
+        self.treasury = params.treasury;
+        self.creation_fee = params.creation_fee;
+        self.cancellation_fee = params.cancellation_fee;
+        self.cancellation_fee_bps = params.cancellation_fee_bps;
+        self.max_duration = params.max_duration;
+        self.min_deposit = params.min_deposit;
+        self.max_receive = params.max_receive;
+        self.take_fee_bps = params.take_fee_bps;
+        self.protocol_mint = params.protocol_mint;
+        self.protocol_fee_discount_bps = params.protocol_fee_discount_bps;
+        self.burn_protocol_fee = params.burn_protocol_fee;
+        self.referral_share_bps = params.referral_share_bps;
+        self.timelock = params.timelock;
+        self.admin_cpi_only = params.admin_cpi_only;
+        self.reject_freezable_mints = params.reject_freezable_mints;
+        self.match_incentive_bps = params.match_incentive_bps;
+        self.auto_refund_tip = params.auto_refund_tip;
+        self.max_creations_per_epoch = params.max_creations_per_epoch;
+        self.min_deposit_tokens = params.min_deposit_tokens;
+        self.dust_threshold = params.dust_threshold;
+        self.cpi_allowlist = params.cpi_allowlist;
+        self.compliance_signer = params.compliance_signer;
+        self.max_frontend_fee_bps = params.max_frontend_fee_bps;
+        self.insurance_premium_bps = params.insurance_premium_bps;
+        self.stake_tiers = params.stake_tiers;
+    }
+
+    /// Take fee discount for a taker with `staked` protocol tokens.
+    pub fn stake_discount_bps(&self, staked: u64) -> u16 {
+        self.stake_tiers
+            .iter()
+            .filter(|tier| tier.discount_bps > 0 && staked >= tier.min_stake)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Take fee on `price` at the escrow's override, or `take_fee_bps` without one, less the
+    /// discount for the taker's stake. Every fill path and `quote_with_fees` charge this.
+    pub fn take_fee(
+        &self,
+        price: u64,
+        fee_override: Option<&FeeOverride>,
+        fee_stake: Option<&FeeStake>,
+    ) -> Result<u64> {
+        let fee_bps = match fee_override {
+            Some(fee_override) => fee_override.fee_bps,
+            None => self.take_fee_bps,
+        };
+        let fee = bps_of(price, fee_bps)?;
+
+        let staked = match fee_stake {
+            Some(fee_stake) => fee_stake.effective_amount(Clock::get()?.slot),
+            None => 0,
+        };
+        fee.checked_sub(bps_of(fee, self.stake_discount_bps(staked))?)
+            .ok_or(EscrowError::Overflow.into())
+    }
+
+    /// Premium on `price` paid on top by a taker who insures their take.
+    pub fn insurance_premium(&self, price: u64) -> Result<u64> {
+        bps_of(price, self.insurance_premium_bps)
+    }
+
+    pub fn is_admin_invocation_allowed(&self) -> bool {
+        !self.admin_cpi_only || get_stack_height() > TRANSACTION_LEVEL_STACK_HEIGHT
+    }
+
+    /// Whether applying `params` would make the protocol less favourable to its users, in which
+    /// case the change has to go through the timelock.
+    pub fn requires_timelock(&self, params: &ConfigParams) -> bool {
+        // Zero means "no cap" for these limits.
+        let tightens = |current: u64, new: u64| new != 0 && (current == 0 || new < current);
+
+        params.creation_fee > self.creation_fee
+            || params.cancellation_fee > self.cancellation_fee
+            || params.cancellation_fee_bps > self.cancellation_fee_bps
+            || params.take_fee_bps > self.take_fee_bps
+            || params.protocol_fee_discount_bps < self.protocol_fee_discount_bps
+            || params.min_deposit > self.min_deposit
+            || tightens(self.max_receive, params.max_receive)
+            || tightens(self.max_duration as u64, params.max_duration as u64)
+            || params.timelock < self.timelock
+            || (self.admin_cpi_only && !params.admin_cpi_only)
+            || (!self.reject_freezable_mints && params.reject_freezable_mints)
+            || params.match_incentive_bps > self.match_incentive_bps
+            || params.auto_refund_tip > self.auto_refund_tip
+            || params.min_deposit_tokens > self.min_deposit_tokens
+            || params.cpi_allowlist != self.cpi_allowlist
+            || params.compliance_signer != self.compliance_signer
+            || params.max_frontend_fee_bps > self.max_frontend_fee_bps
+            || params.insurance_premium_bps > self.insurance_premium_bps
+            || params.stake_tiers != self.stake_tiers
+            || tightens(
+                self.max_creations_per_epoch as u64,
+                params.max_creations_per_epoch as u64,
+            )
+    }
+
+    pub fn validate_make(
+        &self,
+        deposit: u64,
+        receive: u64,
+        params: &EscrowParams,
+        now: i64,
+    ) -> Result<()> {
+        self.validate_terms(deposit, receive, params.expires_at, now)?;
+        require!(
+            !params.require_compliance || self.compliance_signer != Pubkey::default(),
+            EscrowError::InvalidEscrowMode
+        );
+        require!(
+            params.frontend == Pubkey::default()
+                || params.frontend_fee_bps <= self.max_frontend_fee_bps,
+            EscrowError::FrontendFeeTooHigh
+        );
+        Ok(())
+    }
+
+    /// Limits on the deposit, price and lifetime shared by every kind of offer. Offers that can't
+    /// expire pass an `expires_at` of zero.
+    pub fn validate_terms(
+        &self,
+        deposit: u64,
+        receive: u64,
+        expires_at: i64,
+        now: i64,
+    ) -> Result<()> {
+        require_gte!(deposit, self.min_deposit, EscrowError::DepositTooSmall);
+        if self.max_receive != 0 {
+            require_gte!(self.max_receive, receive, EscrowError::ReceiveTooLarge);
+        }
+        if self.max_duration != 0 {
+            require!(
+                expires_at != 0 && expires_at <= now.saturating_add(self.max_duration),
+                EscrowError::InvalidDuration
+            );
+        }
+        Ok(())
+    }
+
+    /// `min_deposit_tokens` in raw units of a mint with `decimals`, saturating for huge values.
+    pub fn validate_deposit_tokens(&self, deposit: u64, decimals: u8) -> Result<()> {
+        let min = 10u64
+            .checked_pow(decimals as u32)
+            .and_then(|scale| self.min_deposit_tokens.checked_mul(scale))
+            .unwrap_or(u64::MAX);
+        require_gte!(deposit, min, EscrowError::DepositTooSmall);
+        Ok(())
+    }
+
+    /// When the allowlist is set, a take invoked through CPI must come directly from a listed
+    /// program. The instructions sysvar only records top-level instructions, so the caller is
+    /// known only one level down, where it is the top-level instruction's program; deeper CPIs
+    /// are rejected rather than attributed to a program further up the stack.
+    pub fn check_cpi_caller(&self, instructions: Option<&AccountInfo>) -> Result<()> {
+        let stack_height = get_stack_height();
+        if stack_height == TRANSACTION_LEVEL_STACK_HEIGHT
+            || self.cpi_allowlist.iter().all(|id| *id == Pubkey::default())
+        {
+            return Ok(());
+        }
+        require_eq!(
+            stack_height,
+            TRANSACTION_LEVEL_STACK_HEIGHT + 1,
+            EscrowError::CpiNotAllowed
+        );
+        let instructions = instructions.ok_or(EscrowError::CpiNotAllowed)?;
+        let current = load_current_index_checked(instructions)?;
+        let caller = load_instruction_at_checked(current as usize, instructions)?.program_id;
+        require!(
+            caller != Pubkey::default() && self.cpi_allowlist.contains(&caller),
+            EscrowError::CpiNotAllowed
+        );
+        Ok(())
+    }
+
+    pub fn validate_mint(&self, mint: &Mint) -> Result<()> {
+        if self.reject_freezable_mints {
+            require!(
+                mint.freeze_authority.is_none(),
+                EscrowError::FreezableMintNotAllowed
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> Config {
+        let mut config = Config::deserialize(&mut &vec![0; Config::INIT_SPACE][..]).unwrap();
+        config.take_fee_bps = 30;
+        config.max_receive = 1_000;
+        config.admin_cpi_only = true;
+        config
+    }
+
+    #[test]
+    fn unchanged_params_skip_the_timelock() {
+        let config = config();
+        assert!(!config.requires_timelock(&config.params()));
+    }
+
+    #[test]
+    fn fee_increases_require_the_timelock() {
+        let config = config();
+        let mut params = config.params();
+        params.take_fee_bps = 29;
+        assert!(!config.requires_timelock(&params));
+        params.take_fee_bps = 31;
+        assert!(config.requires_timelock(&params));
+    }
+
+    #[test]
+    fn only_tighter_caps_require_the_timelock() {
+        let config = config();
+        let mut params = config.params();
+        for (max_receive, tightens) in [(1_001, false), (0, false), (999, true)] {
+            params.max_receive = max_receive;
+            assert_eq!(config.requires_timelock(&params), tightens);
+        }
+
+        let mut uncapped = config.clone();
+        uncapped.max_receive = 0;
+        params.max_receive = u64::MAX;
+        assert!(uncapped.requires_timelock(&params));
+    }
+
+    #[test]
+    fn dropping_protections_requires_the_timelock() {
+        let config = config();
+        let mut params = config.params();
+        params.admin_cpi_only = false;
+        assert!(config.requires_timelock(&params));
+    }
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Marks `address` as blocked from making or taking escrows; its absence means allowed.
+#[account]
+#[derive(InitSpace)]
+pub struct DenylistEntry {
+    pub address: Pubkey,
+    pub bump: u8,
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Shortest delay, in seconds, between queueing and executing an emergency withdrawal, so makers
+/// always have time to react even when `Config::timelock` is zero.
+pub const MIN_EMERGENCY_DELAY: i64 = 24 * 60 * 60;
+
+#[account]
+#[derive(InitSpace)]
+pub struct EmergencyWithdrawal {
+    pub escrow: Pubkey,
+    pub execute_after: i64,
+    pub bump: u8,
+}
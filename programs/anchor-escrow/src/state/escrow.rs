@@ -0,0 +1,820 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        hash::hashv,
+        instruction::{get_stack_height, TRANSACTION_LEVEL_STACK_HEIGHT},
+    },
+};
+
+use crate::{bps_of, mul_div, same_ratio, Config, EscrowError, Rounding};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Escrow {
+    pub seed: [u8; 32],
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    /// Deposit in raw mint_a units; for interest-bearing mints the UI amount drifts over time.
+    pub amount: u64,
+    /// Price in raw mint_b units, independent of any interest accrued by mint_b.
+    pub receive: u64,
+    pub delegated: bool,
+    pub note: [u8; 64],
+    /// URI of an off-chain document describing the deal terms, zero-padded; all zeroes if unset.
+    pub metadata_uri: [u8; 128],
+    /// Unix timestamp before which the maker cannot refund.
+    pub locked_until: i64,
+    /// Unix timestamp before which the escrow cannot be taken.
+    pub starts_at: i64,
+    /// Unix timestamp from which the escrow can no longer be taken; zero if it never expires.
+    pub expires_at: i64,
+    pub created_at: i64,
+    /// Set by the admin to block takes while a compliance report is investigated.
+    pub frozen: bool,
+    pub bump: u8,
+    /// Layout version; accounts created before this field existed read as zero until migrated.
+    pub version: u8,
+    /// Route the maker's payment through a temporary wSOL account and pay it out as native SOL.
+    pub unwrap_sol: bool,
+    /// Alternative mints the maker accepts instead of mint_b, each at its own price.
+    pub payment_options: [PaymentOption; MAX_PAYMENT_OPTIONS],
+    /// Allow `take_partial`; `amount` and `receive` then track what is left.
+    pub allow_partial_fills: bool,
+    /// Discounts for larger partial fills; the deepest tier the fill reaches applies.
+    pub price_tiers: [PriceTier; MAX_PRICE_TIERS],
+    /// Seconds over which a taken deposit unlocks to the taker via `claim_stream`; zero to hand it
+    /// over at take time.
+    pub stream_duration: i64,
+    /// Taker of a streaming escrow, or the default key until it is taken.
+    pub stream_taker: Pubkey,
+    pub stream_started_at: i64,
+    /// Raw mint_a units already claimed by `stream_taker`.
+    pub streamed: u64,
+    /// Operational key that may update, extend and refund the escrow on the maker's behalf; the
+    /// default key if none. Proceeds and refunds still only go to the maker.
+    pub delegate: Pubkey,
+    /// Only allow takes invoked directly in a transaction, not through another program.
+    pub reject_cpi: bool,
+    /// Token account holding the deposit; the default key for delegated escrows and for escrows
+    /// made before this field existed, whose vault is only checked by ATA derivation.
+    pub vault: Pubkey,
+    /// Takers allowed to take a private deal; unused slots hold the default key, and a list of
+    /// only default keys lets anyone take.
+    pub allowed_takers: [Pubkey; MAX_ALLOWED_TAKERS],
+    /// Sell to the bid the maker accepts with `accept_bid` instead of the first taker.
+    pub sealed_bids: bool,
+    /// Recipients of a share of the maker's proceeds from `take`; the maker keeps the rest.
+    /// Unused slots hold the default key.
+    pub payout_splits: [PayoutSplit; MAX_PAYOUT_SPLITS],
+    /// Receives `donation_bps` of the maker's proceeds from `take`; the default key if none.
+    pub donation_recipient: Pubkey,
+    pub donation_bps: u16,
+    /// Price in raw mint_a units of the reverse escrow that `take` opens with the maker's
+    /// proceeds; zero to pay the maker directly.
+    pub relist_receive: u64,
+    /// On-chain state that must hold for `take` to succeed.
+    pub condition: TakeCondition,
+    /// Mint_a units per scheduled fill; the last fill takes whatever is left.
+    pub tranche: u64,
+    /// Seconds between scheduled fills, turning the escrow into a DCA-style agreement filled by
+    /// its allowed takers one `tranche` at a time; zero for unscheduled partial fills.
+    pub fill_interval: i64,
+    /// Unix timestamp of the latest partial fill; zero before the first.
+    pub last_fill_ts: i64,
+    /// Second key that must sign every take alongside the taker, e.g. a risk officer; the
+    /// default key if none.
+    pub co_signer: Pubkey,
+    /// Require `Config::compliance_signer` to co-sign every take, e.g. after an off-chain check.
+    pub require_compliance: bool,
+    /// Credential, such as a Civic pass or an attestation, the taker must hold to take.
+    pub credential: CredentialGate,
+    /// Frontend paid `frontend_fee_bps` of the price by `take`, capped by
+    /// `Config::max_frontend_fee_bps`; the default key if none.
+    pub frontend: Pubkey,
+    pub frontend_fee_bps: u16,
+    /// When `update_escrow`, `extend_escrow` or `merge_escrows` last changed the terms, and how
+    /// many times they have; both zero until the first change.
+    pub last_updated_at: i64,
+    pub revision: u32,
+    /// Zeroed space for new fields, which are carved from its front so adding them needs no
+    /// migration. Only when it runs low does the account grow, with a `VERSION` bump and
+    /// `migrate_escrow`, to restore it.
+    pub reserved: [u8; 64],
+}
+
+pub const MAX_PAYMENT_OPTIONS: usize = 3;
+
+/// An alternative payment mint and price; unused slots hold the default key.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PaymentOption {
+    pub mint: Pubkey,
+    pub receive: u64,
+}
+
+pub const MAX_PRICE_TIERS: usize = 4;
+
+pub const MAX_ALLOWED_TAKERS: usize = 8;
+
+pub const MAX_PAYOUT_SPLITS: usize = 4;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, PartialEq, Eq, InitSpace)]
+pub enum Comparison {
+    #[default]
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+/// Requires the `size`-byte little-endian unsigned integer at `offset` in `account`'s data to
+/// compare to `value` as `comparison`, e.g. a governance proposal's state. Unused if `account`
+/// is the default key.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct TakeCondition {
+    pub account: Pubkey,
+    pub offset: u32,
+    pub size: u8,
+    pub comparison: Comparison,
+    pub value: u64,
+}
+
+impl TakeCondition {
+    pub fn is_set(&self) -> bool {
+        self.account != Pubkey::default()
+    }
+
+    pub fn check(&self, account: Option<&AccountInfo>) -> Result<()> {
+        if !self.is_set() {
+            return Ok(());
+        }
+        let account = account
+            .filter(|account| *account.key == self.account)
+            .ok_or(EscrowError::ConditionNotMet)?;
+        let data = account.try_borrow_data()?;
+        let start = self.offset as usize;
+        let bytes = data
+            .get(start..start + self.size as usize)
+            .ok_or(EscrowError::ConditionNotMet)?;
+
+        let mut buffer = [0u8; 8];
+        buffer[..bytes.len()].copy_from_slice(bytes);
+        let actual = u64::from_le_bytes(buffer);
+
+        let holds = match self.comparison {
+            Comparison::Equal => actual == self.value,
+            Comparison::NotEqual => actual != self.value,
+            Comparison::Less => actual < self.value,
+            Comparison::LessOrEqual => actual <= self.value,
+            Comparison::Greater => actual > self.value,
+            Comparison::GreaterOrEqual => actual >= self.value,
+        };
+        require!(holds, EscrowError::ConditionNotMet);
+        Ok(())
+    }
+}
+
+/// Accepts a credential account owned by `program` that holds the taker's key at
+/// `owner_offset` and, unless `issuer` is the default key, `issuer` at `issuer_offset`, e.g. a
+/// gatekeeper network or attestation schema. Unused if `program` is the default key.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct CredentialGate {
+    pub program: Pubkey,
+    pub owner_offset: u32,
+    pub issuer: Pubkey,
+    pub issuer_offset: u32,
+}
+
+impl CredentialGate {
+    pub fn is_set(&self) -> bool {
+        self.program != Pubkey::default()
+    }
+
+    pub fn check(&self, credential: Option<&AccountInfo>, taker: &Pubkey) -> Result<()> {
+        if !self.is_set() {
+            return Ok(());
+        }
+        let credential = credential
+            .filter(|credential| *credential.owner == self.program)
+            .ok_or(EscrowError::InvalidCredential)?;
+        let data = credential.try_borrow_data()?;
+        let holds = |offset: u32, key: &Pubkey| {
+            let start = offset as usize;
+            data.get(start..start + 32) == Some(key.as_ref())
+        };
+
+        require!(
+            holds(self.owner_offset, taker)
+                && (self.issuer == Pubkey::default() || holds(self.issuer_offset, &self.issuer)),
+            EscrowError::InvalidCredential
+        );
+        Ok(())
+    }
+}
+
+/// Pays `share_bps` of the maker's proceeds, after fees, to `recipient`'s mint_b account.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PayoutSplit {
+    pub recipient: Pubkey,
+    pub share_bps: u16,
+}
+
+/// Fills of at least `min_fill` mint_a units pay `discount_bps` less than the pro-rata price.
+/// Unused tiers are all zero.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct PriceTier {
+    pub min_fill: u64,
+    pub discount_bps: u16,
+}
+
+/// Optional terms supplied by the maker at creation.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct EscrowParams {
+    pub note: [u8; 64],
+    pub metadata_uri: [u8; 128],
+    pub locked_until: i64,
+    pub starts_at: i64,
+    pub expires_at: i64,
+    /// Accept a mint_a with a Token-2022 permanent delegate, which can move the deposit at will.
+    pub allow_permanent_delegate: bool,
+    pub unwrap_sol: bool,
+    pub payment_options: [PaymentOption; MAX_PAYMENT_OPTIONS],
+    pub allow_partial_fills: bool,
+    pub price_tiers: [PriceTier; MAX_PRICE_TIERS],
+    pub stream_duration: i64,
+    pub delegate: Pubkey,
+    pub reject_cpi: bool,
+    pub allowed_takers: [Pubkey; MAX_ALLOWED_TAKERS],
+    pub sealed_bids: bool,
+    pub payout_splits: [PayoutSplit; MAX_PAYOUT_SPLITS],
+    pub donation_recipient: Pubkey,
+    pub donation_bps: u16,
+    pub relist_receive: u64,
+    pub condition: TakeCondition,
+    pub tranche: u64,
+    pub fill_interval: i64,
+    pub co_signer: Pubkey,
+    pub require_compliance: bool,
+    pub credential: CredentialGate,
+    pub frontend: Pubkey,
+    pub frontend_fee_bps: u16,
+}
+
+impl EscrowParams {
+    pub fn validate_tiers(&self) -> Result<()> {
+        for tier in &self.price_tiers {
+            require!(tier.discount_bps <= 10_000, EscrowError::InvalidBps);
+        }
+        Ok(())
+    }
+
+    pub fn validate_stream(&self) -> Result<()> {
+        require!(self.stream_duration >= 0, EscrowError::InvalidDuration);
+        require!(
+            self.stream_duration == 0 || !self.allow_partial_fills,
+            EscrowError::InvalidEscrowMode
+        );
+        Ok(())
+    }
+
+    /// Splits, donations and frontend fees are paid by `take` alone, so they can't be combined
+    /// with the other ways to fill.
+    pub fn validate_splits(&self) -> Result<()> {
+        let donation_bps = if self.donation_recipient == Pubkey::default() {
+            0
+        } else {
+            self.donation_bps as u32
+        };
+        let total = self
+            .payout_splits
+            .iter()
+            .filter(|split| split.recipient != Pubkey::default())
+            .map(|split| split.share_bps as u32)
+            .sum::<u32>()
+            + donation_bps;
+        require!(total <= 10_000, EscrowError::InvalidBps);
+        require!(
+            (total == 0 && self.frontend == Pubkey::default())
+                || (!self.allow_partial_fills && !self.sealed_bids),
+            EscrowError::InvalidEscrowMode
+        );
+        Ok(())
+    }
+
+    /// Conditions are checked by `take` alone, like splits.
+    pub fn validate_condition(&self) -> Result<()> {
+        if !self.condition.is_set() {
+            return Ok(());
+        }
+        require!(
+            (1..=8).contains(&self.condition.size),
+            EscrowError::InvalidCondition
+        );
+        require!(
+            !self.allow_partial_fills && !self.sealed_bids,
+            EscrowError::InvalidEscrowMode
+        );
+        Ok(())
+    }
+
+    /// Scheduled fills go through `take_partial` to a designated taker, so they need partial
+    /// fills and an allowlist.
+    pub fn validate_schedule(&self) -> Result<()> {
+        require!(self.fill_interval >= 0, EscrowError::InvalidDuration);
+        if self.fill_interval == 0 {
+            return Ok(());
+        }
+        require!(
+            self.allow_partial_fills
+                && self.tranche > 0
+                && self
+                    .allowed_takers
+                    .iter()
+                    .any(|key| *key != Pubkey::default()),
+            EscrowError::InvalidEscrowMode
+        );
+        Ok(())
+    }
+
+    /// Only `take` has accounts for the co-signer, the compliance signer and the credential.
+    pub fn validate_co_signer(&self) -> Result<()> {
+        require!(
+            (self.co_signer == Pubkey::default()
+                && !self.require_compliance
+                && !self.credential.is_set())
+                || (!self.allow_partial_fills && !self.sealed_bids),
+            EscrowError::InvalidEscrowMode
+        );
+        Ok(())
+    }
+
+    /// A relist is opened with the proceeds as `take` pays them, so the proceeds can't be
+    /// unwrapped, the deposit streamed or filled in parts, or sold by bid.
+    pub fn validate_relist(&self) -> Result<()> {
+        require!(
+            self.relist_receive == 0
+                || (!self.unwrap_sol
+                    && self.stream_duration == 0
+                    && !self.allow_partial_fills
+                    && !self.sealed_bids),
+            EscrowError::InvalidEscrowMode
+        );
+        Ok(())
+    }
+
+    /// Bids buy the whole deposit at once, so they can't be combined with fills or streams.
+    pub fn validate_bids(&self) -> Result<()> {
+        require!(
+            !self.sealed_bids || (!self.allow_partial_fills && self.stream_duration == 0),
+            EscrowError::InvalidEscrowMode
+        );
+        Ok(())
+    }
+}
+
+/// An escrow as written by the first release, after its discriminator. Only read by
+/// `refund_legacy`, which signs for its vault with the 8-byte seed.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyEscrow {
+    pub seed: u64,
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+}
+
+impl Escrow {
+    pub const VERSION: u8 = 17;
+
+    /// Size of escrows written by the first release: a u64 seed, the maker, both mints,
+    /// `receive` and the bump. Their addresses derive from the 8-byte seed, so they can't be
+    /// upgraded in place.
+    pub const BASELINE_LEN: usize = 8 + 8 + 3 * 32 + 8 + 1;
+
+    /// Sizes of the released layouts `migrate_escrow` upgrades. Each is a prefix of the current
+    /// layout, since fields are only carved from `reserved` or appended after it, so growing the
+    /// account with zeroes reads every newer field as its default.
+    pub const MIGRATABLE_LENS: [usize; 1] = [8 + Escrow::INIT_SPACE];
+
+    /// Rejects escrow data of `len` bytes unless it is in one of `MIGRATABLE_LENS`. First-release
+    /// escrows are refunded with `refund_legacy` instead.
+    pub fn check_migratable(len: usize) -> Result<()> {
+        require!(len != Self::BASELINE_LEN, EscrowError::LegacyEscrow);
+        require!(
+            Self::MIGRATABLE_LENS.contains(&len),
+            EscrowError::UnsupportedVersion
+        );
+        Ok(())
+    }
+
+    pub fn check_takeable(&self, taker: &Pubkey, now: i64, config: &Config) -> Result<()> {
+        require!(!self.sealed_bids, EscrowError::InvalidEscrowMode);
+        self.check_open(taker, now, config)
+    }
+
+    /// Checks shared by takes and by bids on sealed-bid escrows.
+    pub fn check_open(&self, taker: &Pubkey, now: i64, config: &Config) -> Result<()> {
+        require!(!self.is_streaming(), EscrowError::AlreadyTaken);
+        require!(!self.frozen, EscrowError::EscrowFrozen);
+        require!(self.allows_taker(taker), EscrowError::TakerNotAllowed);
+        require!(
+            !self.reject_cpi || get_stack_height() == TRANSACTION_LEVEL_STACK_HEIGHT,
+            EscrowError::CpiNotAllowed
+        );
+        require!(now >= self.starts_at, EscrowError::NotStarted);
+        require!(
+            !self.is_expired(now, config.max_duration),
+            EscrowError::Expired
+        );
+        Ok(())
+    }
+
+    /// Whether `key` is the token account recorded as this escrow's vault.
+    pub fn holds_vault(&self, key: &Pubkey) -> bool {
+        self.vault == Pubkey::default() || *key == self.vault
+    }
+
+    /// Whether `taker` may take this escrow under its allowlist.
+    pub fn allows_taker(&self, taker: &Pubkey) -> bool {
+        self.allowed_takers
+            .iter()
+            .all(|key| *key == Pubkey::default())
+            || self.allowed_takers.contains(taker)
+    }
+
+    /// Payout splits in use, in the order their accounts are passed to `take`.
+    pub fn active_splits(&self) -> impl Iterator<Item = &PayoutSplit> {
+        self.payout_splits
+            .iter()
+            .filter(|split| split.recipient != Pubkey::default())
+    }
+
+    /// The frontend fee on `price`, taken from it like the take fee.
+    pub fn frontend_fee(&self, price: u64) -> Result<u64> {
+        if self.frontend == Pubkey::default() {
+            return Ok(0);
+        }
+        bps_of(price, self.frontend_fee_bps)
+    }
+
+    /// Each active payout split's share of `proceeds`, the maker's proceeds after fees.
+    pub fn split_amounts(&self, proceeds: u64) -> Result<Vec<(Pubkey, u64)>> {
+        self.active_splits()
+            .map(|split| Ok((split.recipient, bps_of(proceeds, split.share_bps)?)))
+            .collect()
+    }
+
+    /// The donation out of `proceeds`, the maker's proceeds after fees.
+    pub fn donation(&self, proceeds: u64) -> Result<u64> {
+        if self.donation_recipient == Pubkey::default() {
+            return Ok(0);
+        }
+        bps_of(proceeds, self.donation_bps)
+    }
+
+    /// What the maker keeps of `proceeds`, the proceeds after fees, once payout splits and the
+    /// donation are paid.
+    pub fn maker_proceeds(&self, proceeds: u64) -> Result<u64> {
+        let splits = self
+            .split_amounts(proceeds)?
+            .iter()
+            .try_fold(0u64, |total, (_, amount)| total.checked_add(*amount))
+            .ok_or(EscrowError::Overflow)?;
+        proceeds
+            .checked_sub(splits)
+            .ok_or(EscrowError::Overflow)?
+            .checked_sub(self.donation(proceeds)?)
+            .ok_or(EscrowError::Overflow.into())
+    }
+
+    /// Widens `fill` to the whole remainder if it would otherwise leave less than
+    /// `dust_threshold` behind.
+    pub fn sweep_dust(&self, fill: u64, dust_threshold: u64) -> u64 {
+        let remainder = self.amount.saturating_sub(fill);
+        if remainder > 0 && remainder < dust_threshold {
+            self.amount
+        } else {
+            fill
+        }
+    }
+
+    /// Whether the escrow can only be filled through `take`, which alone pays splits, donations
+    /// and frontend fees, checks the take condition, co-signers and credential, streams the
+    /// deposit and opens relists.
+    pub fn take_only(&self) -> bool {
+        self.stream_duration > 0
+            || self.relist_receive > 0
+            || self.active_splits().next().is_some()
+            || self.donation_recipient != Pubkey::default()
+            || self.condition.is_set()
+            || self.co_signer != Pubkey::default()
+            || self.require_compliance
+            || self.credential.is_set()
+            || self.frontend != Pubkey::default()
+    }
+
+    /// Stamps a change to the terms so indexers and takers can detect it.
+    pub fn record_update(&mut self, now: i64) {
+        self.last_updated_at = now;
+        self.revision = self.revision.wrapping_add(1);
+    }
+
+    /// Whether fills are limited to one `tranche` per `fill_interval`.
+    pub fn is_scheduled(&self) -> bool {
+        self.fill_interval > 0
+    }
+
+    /// Seed of the reverse escrow opened when this one is taken with a relist price set.
+    pub fn relist_seed(&self) -> [u8; 32] {
+        hashv(&[b"relist", &self.seed]).to_bytes()
+    }
+
+    /// Whether `key` is the maker or the maker's delegate.
+    pub fn is_manager(&self, key: &Pubkey) -> bool {
+        *key == self.maker || (self.delegate != Pubkey::default() && *key == self.delegate)
+    }
+
+    /// Whether the escrow has been taken and is releasing its deposit to `stream_taker`.
+    pub fn is_streaming(&self) -> bool {
+        self.stream_taker != Pubkey::default()
+    }
+
+    /// Raw mint_a units unlocked to the stream taker by `now`.
+    pub fn vested(&self, now: i64) -> Result<u64> {
+        let elapsed = now.saturating_sub(self.stream_started_at).max(0);
+        if elapsed >= self.stream_duration {
+            return Ok(self.amount);
+        }
+        mul_div(
+            self.amount,
+            elapsed as u64,
+            self.stream_duration as u64,
+            Rounding::Down,
+        )
+    }
+
+    /// Payment mint and price at `index`, where zero is mint_b and the rest are `payment_options`.
+    pub fn payment_option(&self, index: u8) -> Option<PaymentOption> {
+        match index {
+            0 => Some(PaymentOption {
+                mint: self.mint_b,
+                receive: self.receive,
+            }),
+            _ => self
+                .payment_options
+                .get(index as usize - 1)
+                .filter(|option| option.mint != Pubkey::default())
+                .copied(),
+        }
+    }
+
+    /// Price of the escrow when paid in `mint`.
+    pub fn receive_in(&self, mint: &Pubkey) -> Result<u64> {
+        (0..=MAX_PAYMENT_OPTIONS as u8)
+            .filter_map(|index| self.payment_option(index))
+            .find(|option| option.mint == *mint)
+            .map(|option| option.receive)
+            .ok_or(EscrowError::InvalidPaymentOption.into())
+    }
+
+    /// Pro-rata share of `receive` for `fill` mint_a units, rounded up in the maker's favour.
+    pub fn base_price(&self, fill: u64) -> Result<u64> {
+        require!(
+            fill > 0 && fill <= self.amount,
+            EscrowError::InvalidFillAmount
+        );
+        mul_div(self.receive, fill, self.amount, Rounding::Up)
+    }
+
+    /// Price of a partial fill after the best tier it qualifies for.
+    pub fn fill_price(&self, fill: u64) -> Result<u64> {
+        let base = self.base_price(fill)?;
+        let discount_bps = self
+            .price_tiers
+            .iter()
+            .filter(|tier| tier.discount_bps > 0 && fill >= tier.min_fill)
+            .map(|tier| tier.discount_bps)
+            .max()
+            .unwrap_or(0);
+        Ok(base
+            .checked_sub(bps_of(base, discount_bps)?)
+            .ok_or(EscrowError::Overflow)?)
+    }
+
+    /// This escrow's terms for a new `deposit` re-based to `now`: timestamps keep their offsets
+    /// from `created_at` and payment option prices scale with the deposit.
+    pub fn repost_params(&self, now: i64, deposit: u64) -> Result<EscrowParams> {
+        let shift = |timestamp: i64| {
+            if timestamp == 0 {
+                0
+            } else {
+                now.saturating_add(timestamp.saturating_sub(self.created_at))
+            }
+        };
+
+        let mut payment_options = self.payment_options;
+        for option in payment_options.iter_mut() {
+            option.receive = self.scale_price(option.receive, deposit)?;
+        }
+
+        Ok(EscrowParams {
+            note: self.note,
+            metadata_uri: self.metadata_uri,
+            locked_until: shift(self.locked_until),
+            starts_at: shift(self.starts_at),
+            expires_at: shift(self.expires_at),
+            // The mint already passed validation when this escrow was made.
+            allow_permanent_delegate: true,
+            unwrap_sol: self.unwrap_sol,
+            payment_options,
+            allow_partial_fills: self.allow_partial_fills,
+            price_tiers: self.price_tiers,
+            stream_duration: self.stream_duration,
+            delegate: self.delegate,
+            reject_cpi: self.reject_cpi,
+            allowed_takers: self.allowed_takers,
+            sealed_bids: self.sealed_bids,
+            payout_splits: self.payout_splits,
+            donation_recipient: self.donation_recipient,
+            donation_bps: self.donation_bps,
+            relist_receive: self.relist_receive,
+            condition: self.condition,
+            tranche: self.tranche,
+            fill_interval: self.fill_interval,
+            co_signer: self.co_signer,
+            require_compliance: self.require_compliance,
+            credential: self.credential,
+            frontend: self.frontend,
+            frontend_fee_bps: self.frontend_fee_bps,
+        })
+    }
+
+    /// `price`, quoted for this escrow's `amount`, re-quoted for `deposit`, rounded up.
+    pub fn scale_price(&self, price: u64, deposit: u64) -> Result<u64> {
+        require!(self.amount > 0, EscrowError::InvalidFillAmount);
+        mul_div(price, deposit, self.amount, Rounding::Up)
+    }
+
+    /// Whether `other` sells the same pair at the same unit price, in every payment option.
+    pub fn same_price_as(&self, other: &Escrow) -> bool {
+        let cross = |a: u64, b: u64| same_ratio(a, self.amount, b, other.amount);
+
+        self.mint_a == other.mint_a
+            && self.mint_b == other.mint_b
+            && cross(self.receive, other.receive)
+            && self
+                .payment_options
+                .iter()
+                .zip(other.payment_options.iter())
+                .all(|(mine, theirs)| {
+                    mine.mint == theirs.mint && cross(mine.receive, theirs.receive)
+                })
+    }
+
+    /// An escrow is expired once its own expiry passes or it outlives the config's duration cap.
+    pub fn is_expired(&self, now: i64, max_duration: i64) -> bool {
+        (self.expires_at != 0 && now >= self.expires_at)
+            || (max_duration != 0 && now >= self.created_at.saturating_add(max_duration))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An escrow with every field zeroed but its deposit and price.
+    fn escrow(amount: u64, receive: u64) -> Escrow {
+        let mut escrow = Escrow::deserialize(&mut &vec![0; Escrow::INIT_SPACE][..]).unwrap();
+        escrow.amount = amount;
+        escrow.receive = receive;
+        escrow
+    }
+
+    fn condition(account: Pubkey, comparison: Comparison, value: u64) -> TakeCondition {
+        TakeCondition {
+            account,
+            offset: 1,
+            size: 2,
+            comparison,
+            value,
+        }
+    }
+
+    #[test]
+    fn fill_price_rounds_up_in_the_makers_favour() {
+        let escrow = escrow(3, 10);
+        assert_eq!(escrow.fill_price(1).unwrap(), 4);
+        assert_eq!(escrow.fill_price(3).unwrap(), 10);
+        for fill in [0, 4] {
+            assert_eq!(
+                escrow.fill_price(fill).unwrap_err(),
+                EscrowError::InvalidFillAmount.into()
+            );
+        }
+    }
+
+    #[test]
+    fn fill_price_applies_the_best_tier_reached() {
+        let mut escrow = escrow(1_000, 10_000);
+        escrow.price_tiers[0] = PriceTier {
+            min_fill: 100,
+            discount_bps: 100,
+        };
+        escrow.price_tiers[1] = PriceTier {
+            min_fill: 500,
+            discount_bps: 500,
+        };
+        assert_eq!(escrow.fill_price(99).unwrap(), 990);
+        assert_eq!(escrow.fill_price(100).unwrap(), 990);
+        assert_eq!(escrow.fill_price(499).unwrap(), 4_941);
+        assert_eq!(escrow.fill_price(500).unwrap(), 4_750);
+        assert_eq!(escrow.fill_price(1_000).unwrap(), 9_500);
+    }
+
+    #[test]
+    fn take_condition_compares_the_integer_at_its_offset() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        // 0x0102 little-endian at offset 1.
+        let mut data = [0xff, 0x02, 0x01, 0xff];
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        for (comparison, value, holds) in [
+            (Comparison::Equal, 0x0102, true),
+            (Comparison::NotEqual, 0x0102, false),
+            (Comparison::Less, 0x0103, true),
+            (Comparison::LessOrEqual, 0x0101, false),
+            (Comparison::Greater, 0x0101, true),
+            (Comparison::GreaterOrEqual, 0x0103, false),
+        ] {
+            assert_eq!(
+                condition(key, comparison, value)
+                    .check(Some(&account))
+                    .is_ok(),
+                holds
+            );
+        }
+    }
+
+    #[test]
+    fn take_condition_needs_its_account() {
+        let key = Pubkey::new_unique();
+        let owner = Pubkey::new_unique();
+        let mut lamports = 0;
+        let mut data = [0; 2];
+        let account = AccountInfo::new(
+            &key,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &owner,
+            false,
+            0,
+        );
+
+        assert!(TakeCondition::default().check(None).is_ok());
+        let condition_on_key = condition(key, Comparison::Equal, 0);
+        assert!(condition_on_key.check(None).is_err());
+        // Reads past the end of the data.
+        assert!(condition_on_key.check(Some(&account)).is_err());
+        let other = condition(Pubkey::new_unique(), Comparison::Equal, 0);
+        assert!(other.check(Some(&account)).is_err());
+    }
+
+    #[test]
+    fn first_release_escrows_are_not_migratable() {
+        assert_eq!(
+            Escrow::check_migratable(Escrow::BASELINE_LEN).unwrap_err(),
+            EscrowError::LegacyEscrow.into()
+        );
+    }
+
+    #[test]
+    fn unknown_layouts_are_not_migratable() {
+        for len in [0, 8, Escrow::BASELINE_LEN + 1, 8 + Escrow::INIT_SPACE + 1] {
+            assert_eq!(
+                Escrow::check_migratable(len).unwrap_err(),
+                EscrowError::UnsupportedVersion.into()
+            );
+        }
+    }
+
+    #[test]
+    fn current_layout_is_migratable() {
+        assert!(Escrow::check_migratable(8 + Escrow::INIT_SPACE).is_ok());
+    }
+}
@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Take fees paid to the treasury in `mint` during one Solana epoch, so later distributions can
+/// reference exactly what was earned when.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeEpoch {
+    pub mint: Pubkey,
+    pub epoch: u64,
+    pub fees: u64,
+    pub fills: u64,
+    pub bump: u8,
+}
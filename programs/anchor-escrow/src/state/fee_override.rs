@@ -0,0 +1,10 @@
+use anchor_lang::prelude::*;
+
+/// Admin-negotiated take fee for a single escrow, used in place of `Config::take_fee_bps`.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeOverride {
+    pub escrow: Pubkey,
+    pub fee_bps: u16,
+    pub bump: u8,
+}
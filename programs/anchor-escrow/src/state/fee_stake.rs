@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// Protocol tokens `owner` has staked for a take fee discount, held in this account's ATA.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeStake {
+    pub owner: Pubkey,
+    pub amount: u64,
+    /// Slot of the latest stake; the discount only applies from the following slot, so tokens
+    /// can't be staked and unstaked around a take in one transaction.
+    pub staked_slot: u64,
+    pub bump: u8,
+}
+
+impl FeeStake {
+    /// The stake counted towards fee tiers at `slot`.
+    pub fn effective_amount(&self, slot: u64) -> u64 {
+        if slot > self.staked_slot {
+            self.amount
+        } else {
+            0
+        }
+    }
+}
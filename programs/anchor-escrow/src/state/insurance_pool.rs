@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+
+/// Pool of take premiums in one mint, paid out on claims the admin approves. Its vault is the
+/// pool's associated token account.
+#[account]
+#[derive(InitSpace)]
+pub struct InsurancePool {
+    pub mint: Pubkey,
+    /// Premiums collected over the pool's lifetime, in raw units.
+    pub premiums: u64,
+    /// Claims paid over the pool's lifetime, in raw units.
+    pub claims_paid: u64,
+    pub bump: u8,
+}
+
+/// A claimant's request to be paid `amount` from `pool` for a settlement of `escrow` that went
+/// wrong, e.g. a transfer hook that withheld tokens. Closed when approved or rejected.
+#[account]
+#[derive(InitSpace)]
+pub struct InsuranceClaim {
+    pub claimant: Pubkey,
+    pub pool: Pubkey,
+    pub escrow: Pubkey,
+    pub amount: u64,
+    pub bump: u8,
+}
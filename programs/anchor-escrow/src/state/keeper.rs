@@ -0,0 +1,25 @@
+use anchor_lang::prelude::*;
+
+use crate::EscrowError;
+
+/// A registered crank operator. Lamport tips earned by cranks are held in this account until
+/// claimed; `rewards` is the claimable part, excluding rent.
+#[account]
+#[derive(InitSpace)]
+pub struct Keeper {
+    pub authority: Pubkey,
+    pub cranks: u64,
+    pub rewards: u64,
+    pub bump: u8,
+}
+
+impl Keeper {
+    pub fn record_crank(&mut self, reward: u64) -> Result<()> {
+        self.cranks = self.cranks.saturating_add(1);
+        self.rewards = self
+            .rewards
+            .checked_add(reward)
+            .ok_or(EscrowError::Overflow)?;
+        Ok(())
+    }
+}
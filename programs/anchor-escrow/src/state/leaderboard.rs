@@ -0,0 +1,94 @@
+use std::cmp::Reverse;
+
+use anchor_lang::prelude::*;
+
+pub const LEADERBOARD_SIZE: usize = 10;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace)]
+pub struct LeaderboardEntry {
+    pub maker: Pubkey,
+    pub volume: u64,
+}
+
+/// Top makers by volume filled in `mint`, highest first, so rankings render from one fetch.
+#[account]
+#[derive(InitSpace)]
+pub struct Leaderboard {
+    pub mint: Pubkey,
+    #[max_len(LEADERBOARD_SIZE)]
+    pub entries: Vec<LeaderboardEntry>,
+    pub bump: u8,
+}
+
+impl Leaderboard {
+    /// Adds `volume` to `maker`'s entry. Once the board is full, an unranked maker only enters by
+    /// beating the lowest entry with a single fill, so volume filled while unranked isn't counted.
+    pub fn record(&mut self, maker: Pubkey, volume: u64) {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.maker == maker) {
+            entry.volume = entry.volume.saturating_add(volume);
+        } else if self.entries.len() < LEADERBOARD_SIZE {
+            self.entries.push(LeaderboardEntry { maker, volume });
+        } else {
+            match self.entries.last_mut() {
+                Some(lowest) if volume > lowest.volume => {
+                    *lowest = LeaderboardEntry { maker, volume };
+                }
+                _ => return,
+            }
+        }
+        self.entries.sort_by_key(|entry| Reverse(entry.volume));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn leaderboard() -> Leaderboard {
+        Leaderboard {
+            mint: Pubkey::default(),
+            entries: Vec::new(),
+            bump: 0,
+        }
+    }
+
+    fn volumes(leaderboard: &Leaderboard) -> Vec<u64> {
+        leaderboard
+            .entries
+            .iter()
+            .map(|entry| entry.volume)
+            .collect()
+    }
+
+    #[test]
+    fn record_accumulates_and_ranks_highest_first() {
+        let mut leaderboard = leaderboard();
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        leaderboard.record(a, 5);
+        leaderboard.record(b, 7);
+        leaderboard.record(a, 4);
+
+        assert_eq!(leaderboard.entries[0].maker, a);
+        assert_eq!(volumes(&leaderboard), [9, 7]);
+    }
+
+    #[test]
+    fn full_board_only_admits_fills_above_the_lowest_entry() {
+        let mut leaderboard = leaderboard();
+        for volume in 1..=LEADERBOARD_SIZE as u64 {
+            leaderboard.record(Pubkey::new_unique(), volume * 10);
+        }
+
+        let unranked = Pubkey::new_unique();
+        leaderboard.record(unranked, 10);
+        assert!(leaderboard
+            .entries
+            .iter()
+            .all(|entry| entry.maker != unranked));
+
+        leaderboard.record(unranked, 11);
+        assert_eq!(leaderboard.entries.len(), LEADERBOARD_SIZE);
+        assert_eq!(leaderboard.entries.last().unwrap().maker, unranked);
+        assert_eq!(*volumes(&leaderboard).last().unwrap(), 11);
+    }
+}
@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::EscrowError;
+
+/// Creations by a maker in the current epoch, used to rate limit make.
+#[account]
+#[derive(InitSpace)]
+pub struct MakerActivity {
+    pub maker: Pubkey,
+    pub epoch: u64,
+    pub creations: u32,
+    pub bump: u8,
+}
+
+impl MakerActivity {
+    /// Counts a creation in `epoch`, resetting the count when a new epoch starts.
+    pub fn record_creation(&mut self, epoch: u64, max_creations_per_epoch: u32) -> Result<()> {
+        if self.epoch != epoch {
+            self.epoch = epoch;
+            self.creations = 0;
+        }
+        require!(
+            max_creations_per_epoch == 0 || self.creations < max_creations_per_epoch,
+            EscrowError::CreationRateLimited
+        );
+        self.creations += 1;
+        Ok(())
+    }
+}
@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct MakerCounter {
+    pub maker: Pubkey,
+    pub count: u64,
+    pub bump: u8,
+}
+
+impl MakerCounter {
+    pub fn next_seed(&self) -> [u8; 32] {
+        let mut seed = [0u8; 32];
+        seed[..8].copy_from_slice(&self.count.to_le_bytes());
+        seed
+    }
+}
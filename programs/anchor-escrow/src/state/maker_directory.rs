@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::EscrowError;
+
+pub const MAX_DIRECTORY_ESCROWS: usize = 32;
+
+/// Seeds of a maker's open escrows, so wallets can list them with a single fetch. Escrows closed
+/// without the directory passed in stay listed until `unlist_escrow` removes them.
+#[account]
+#[derive(InitSpace)]
+pub struct MakerDirectory {
+    pub maker: Pubkey,
+    #[max_len(MAX_DIRECTORY_ESCROWS)]
+    pub seeds: Vec<[u8; 32]>,
+    pub bump: u8,
+}
+
+impl MakerDirectory {
+    pub fn insert(&mut self, seed: [u8; 32]) -> Result<()> {
+        require!(
+            self.seeds.len() < MAX_DIRECTORY_ESCROWS,
+            EscrowError::DirectoryFull
+        );
+        self.seeds.push(seed);
+        Ok(())
+    }
+
+    pub fn remove(&mut self, seed: &[u8; 32]) {
+        self.seeds.retain(|listed| listed != seed);
+    }
+}
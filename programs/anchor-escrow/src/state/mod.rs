@@ -0,0 +1,86 @@
+pub mod escrow;
+pub use escrow::*;
+
+pub mod template;
+pub use template::*;
+
+pub mod subscription;
+pub use subscription::*;
+
+pub mod stake_escrow;
+pub use stake_escrow::*;
+
+pub mod offer_tree;
+pub use offer_tree::*;
+
+pub mod rfq;
+pub use rfq::*;
+
+pub mod bid;
+pub use bid::*;
+
+pub mod pair_stats;
+pub use pair_stats::*;
+
+pub mod fee_epoch;
+pub use fee_epoch::*;
+
+pub mod points;
+pub use points::*;
+
+pub mod leaderboard;
+pub use leaderboard::*;
+
+pub mod trade_log;
+pub use trade_log::*;
+
+pub mod maker_activity;
+pub use maker_activity::*;
+
+pub mod maker_directory;
+pub use maker_directory::*;
+
+pub mod maker_counter;
+pub use maker_counter::*;
+
+pub mod keeper;
+pub use keeper::*;
+
+pub mod session_token;
+pub use session_token::*;
+
+pub mod config;
+pub use config::*;
+
+pub mod pending_config_change;
+pub use pending_config_change::*;
+
+pub mod emergency_withdrawal;
+pub use emergency_withdrawal::*;
+
+pub mod fee_override;
+pub use fee_override::*;
+
+pub mod swap_router;
+pub use swap_router::*;
+
+pub mod buyback_config;
+pub use buyback_config::*;
+
+pub mod denylist_entry;
+pub use denylist_entry::*;
+
+pub mod protocol_fee_rate;
+pub use protocol_fee_rate::*;
+
+pub mod affiliate;
+pub use affiliate::*;
+
+pub mod insurance_pool;
+pub use insurance_pool::*;
+
+pub mod fee_stake;
+pub use fee_stake::*;
+
+pub mod rebate;
+pub use rebate::*;
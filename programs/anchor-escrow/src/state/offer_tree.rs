@@ -0,0 +1,127 @@
+use anchor_lang::{prelude::*, solana_program::keccak::hashv};
+
+use crate::EscrowError;
+
+pub const OFFER_TREE_DEPTH: usize = 14;
+pub const OFFER_TREE_ROOT_HISTORY: usize = 32;
+
+/// Append-only merkle tree of compressed offers. Deposits for every leaf are pooled in the tree's
+/// ATA per mint_a. Recent roots are kept so proofs built before a concurrent append still verify,
+/// and settled leaves are tracked in a bitmap rather than rewritten.
+#[account(zero_copy)]
+pub struct OfferTree {
+    pub roots: [[u8; 32]; OFFER_TREE_ROOT_HISTORY],
+    pub filled_subtrees: [[u8; 32]; OFFER_TREE_DEPTH],
+    pub spent: [u8; (1 << OFFER_TREE_DEPTH) / 8],
+    pub id: u32,
+    pub next_index: u32,
+    pub root_index: u32,
+    pub bump: u8,
+    pub _padding: [u8; 3],
+}
+
+/// Terms committed to by a leaf of an `OfferTree`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CompressedOffer {
+    pub maker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount: u64,
+    pub receive: u64,
+}
+
+impl CompressedOffer {
+    pub fn leaf(&self, index: u32) -> [u8; 32] {
+        hashv(&[
+            &index.to_le_bytes(),
+            self.maker.as_ref(),
+            self.mint_a.as_ref(),
+            self.mint_b.as_ref(),
+            &self.amount.to_le_bytes(),
+            &self.receive.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    hashv(&[left, right]).to_bytes()
+}
+
+fn zero_hashes() -> [[u8; 32]; OFFER_TREE_DEPTH + 1] {
+    let mut zeros = [[0u8; 32]; OFFER_TREE_DEPTH + 1];
+    for level in 0..OFFER_TREE_DEPTH {
+        zeros[level + 1] = hash_pair(&zeros[level], &zeros[level]);
+    }
+    zeros
+}
+
+impl OfferTree {
+    pub fn initialize(&mut self, id: u32, bump: u8) {
+        let zeros = zero_hashes();
+        self.filled_subtrees
+            .copy_from_slice(&zeros[..OFFER_TREE_DEPTH]);
+        self.roots[0] = zeros[OFFER_TREE_DEPTH];
+        self.id = id;
+        self.bump = bump;
+    }
+
+    pub fn append(&mut self, offer: &CompressedOffer) -> Result<u32> {
+        let index = self.next_index;
+        require!(
+            (index as usize) < 1 << OFFER_TREE_DEPTH,
+            EscrowError::OfferTreeFull
+        );
+
+        let zeros = zero_hashes();
+        let mut node = offer.leaf(index);
+        let mut position = index;
+        for (level, zero) in zeros[..OFFER_TREE_DEPTH].iter().enumerate() {
+            if position & 1 == 0 {
+                self.filled_subtrees[level] = node;
+                node = hash_pair(&node, zero);
+            } else {
+                node = hash_pair(&self.filled_subtrees[level], &node);
+            }
+            position >>= 1;
+        }
+
+        self.root_index = (self.root_index + 1) % OFFER_TREE_ROOT_HISTORY as u32;
+        self.roots[self.root_index as usize] = node;
+        self.next_index += 1;
+        Ok(index)
+    }
+
+    /// Verifies `offer` sits at `index` under a recent root and marks it settled.
+    pub fn settle(
+        &mut self,
+        index: u32,
+        offer: &CompressedOffer,
+        proof: &[[u8; 32]],
+    ) -> Result<()> {
+        require!(
+            proof.len() == OFFER_TREE_DEPTH && index < self.next_index,
+            EscrowError::InvalidProof
+        );
+
+        let mut node = offer.leaf(index);
+        let mut position = index;
+        for sibling in proof {
+            node = if position & 1 == 0 {
+                hash_pair(&node, sibling)
+            } else {
+                hash_pair(sibling, &node)
+            };
+            position >>= 1;
+        }
+        require!(
+            node != [0u8; 32] && self.roots.contains(&node),
+            EscrowError::InvalidProof
+        );
+
+        let (byte, bit) = ((index / 8) as usize, 1u8 << (index % 8));
+        require!(self.spent[byte] & bit == 0, EscrowError::OfferSettled);
+        self.spent[byte] |= bit;
+        Ok(())
+    }
+}
@@ -0,0 +1,15 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PairStats {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub fills: u64,
+    pub volume_a: u128,
+    pub volume_b: u128,
+    pub bump: u8,
+    /// Zeroed space for future fields, which are carved from its front so the account size and
+    /// the offsets of existing fields stay fixed.
+    pub reserved: [u8; 64],
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+use crate::ConfigParams;
+
+#[account]
+#[derive(InitSpace)]
+pub struct PendingConfigChange {
+    pub params: ConfigParams,
+    /// `Config::params_hash` when queued; the change can't execute once the config has moved on,
+    /// as applying it would revert whatever was updated in between.
+    pub config_hash: [u8; 32],
+    pub execute_after: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+use crate::{mul_div, Rounding};
+
+/// Admin-set points earned per raw unit of volume in `mint`, in basis points, so that makes and
+/// takes can be weighted differently during an incentive season. Zero disables accrual.
+#[account]
+#[derive(InitSpace)]
+pub struct PointsRate {
+    pub mint: Pubkey,
+    pub make_multiplier_bps: u64,
+    pub take_multiplier_bps: u64,
+    pub bump: u8,
+}
+
+impl PointsRate {
+    pub fn make_points(&self, volume: u64) -> Result<u64> {
+        mul_div(volume, self.make_multiplier_bps, 10_000, Rounding::Down)
+    }
+
+    pub fn take_points(&self, volume: u64) -> Result<u64> {
+        mul_div(volume, self.take_multiplier_bps, 10_000, Rounding::Down)
+    }
+}
+
+/// Points `owner` has earned making and taking escrows. Both sides are credited when an escrow is
+/// filled through `take`, `take_partial`, `take_exact_out`, `take_delegated`, `take_with_route` or
+/// `accept_bid`; makes earn nothing until then, and other fills don't accrue.
+#[account]
+#[derive(InitSpace)]
+pub struct Points {
+    pub owner: Pubkey,
+    pub points: u64,
+    pub bump: u8,
+}
+
+impl Points {
+    pub fn accrue(&mut self, points: u64) {
+        self.points = self.points.saturating_add(points);
+    }
+}
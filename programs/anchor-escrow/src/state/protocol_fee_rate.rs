@@ -0,0 +1,20 @@
+use anchor_lang::prelude::*;
+
+use crate::{mul_div, Rounding};
+
+/// Exchange rate used to convert a fee denominated in `mint` into protocol tokens.
+#[account]
+#[derive(InitSpace)]
+pub struct ProtocolFeeRate {
+    pub mint: Pubkey,
+    /// Raw protocol token units per `denominator` raw units of `mint`.
+    pub numerator: u64,
+    pub denominator: u64,
+    pub bump: u8,
+}
+
+impl ProtocolFeeRate {
+    pub fn convert(&self, amount: u64) -> Result<u64> {
+        mul_div(amount, self.numerator, self.denominator, Rounding::Down)
+    }
+}
@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+
+pub const MAX_REBATE_TIERS: usize = 4;
+
+/// Takers who have filled at least `min_volume` raw units get `rebate_bps` of the treasury's
+/// share of their take fees back. Unused tiers are all zero.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Default, InitSpace)]
+pub struct RebateTier {
+    pub min_volume: u64,
+    pub rebate_bps: u16,
+}
+
+/// Admin-set rebate tiers for takes paid in `mint`.
+#[account]
+#[derive(InitSpace)]
+pub struct RebateSchedule {
+    pub mint: Pubkey,
+    pub tiers: [RebateTier; MAX_REBATE_TIERS],
+    pub bump: u8,
+}
+
+impl RebateSchedule {
+    /// Rebate for a taker who has already filled `volume`; the deepest tier reached applies.
+    pub fn rebate_bps(&self, volume: u64) -> u16 {
+        self.tiers
+            .iter()
+            .filter(|tier| tier.rebate_bps > 0 && volume >= tier.min_volume)
+            .map(|tier| tier.rebate_bps)
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// A taker's filled volume in `mint` and the rebates accrued in this account's ATA, claimable
+/// with `claim_rebate`.
+#[account]
+#[derive(InitSpace)]
+pub struct TakerRebate {
+    pub taker: Pubkey,
+    pub mint: Pubkey,
+    pub volume: u64,
+    pub accrued: u64,
+    pub bump: u8,
+}
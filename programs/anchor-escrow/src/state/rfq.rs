@@ -0,0 +1,41 @@
+use anchor_lang::prelude::*;
+
+/// Request for `amount` of mint_a, paid in mint_b, open to quotes until `expires_at`. Once
+/// accepted or cancelled it stays `closed` until every quote has been withdrawn, so the PDA can't
+/// be re-created under quotes whose bonds depend on it.
+#[account]
+#[derive(InitSpace)]
+pub struct Rfq {
+    pub seed: [u8; 32],
+    pub requester: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount: u64,
+    /// Lamports each quote must lock, forfeited to the requester if it is pulled early.
+    pub bond: u64,
+    pub expires_at: i64,
+    /// Quotes submitted and not yet accepted or withdrawn.
+    pub open_quotes: u32,
+    pub closed: bool,
+    pub bump: u8,
+}
+
+impl Rfq {
+    /// Counts an accepted or withdrawn quote out, returning whether the RFQ can now be closed.
+    pub fn release_quote(&mut self) -> bool {
+        self.open_quotes = self.open_quotes.saturating_sub(1);
+        self.closed && self.open_quotes == 0
+    }
+}
+
+/// A market maker's firm price for an `Rfq`. The quoted mint_a sits in the quote's vault and the
+/// bond is held as lamports on this account.
+#[account]
+#[derive(InitSpace)]
+pub struct Quote {
+    pub rfq: Pubkey,
+    pub market_maker: Pubkey,
+    pub receive: u64,
+    pub bond: u64,
+    pub bump: u8,
+}
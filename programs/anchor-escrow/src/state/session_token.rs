@@ -0,0 +1,13 @@
+use anchor_lang::prelude::*;
+
+/// Lets `signer`, an ephemeral key held by an app, take escrows on `authority`'s behalf until
+/// `expires_at`. Payments are pulled through this account's token approval on the authority's
+/// mint_b account, capped at the approved allowance.
+#[account]
+#[derive(InitSpace)]
+pub struct SessionToken {
+    pub authority: Pubkey,
+    pub signer: Pubkey,
+    pub expires_at: i64,
+    pub bump: u8,
+}
@@ -0,0 +1,14 @@
+use anchor_lang::prelude::*;
+
+/// Offer of a native stake account, whose staker and withdrawer authorities are held by this PDA
+/// until settlement.
+#[account]
+#[derive(InitSpace)]
+pub struct StakeEscrow {
+    pub seed: [u8; 32],
+    pub maker: Pubkey,
+    pub stake: Pubkey,
+    pub mint_b: Pubkey,
+    pub receive: u64,
+    pub bump: u8,
+}
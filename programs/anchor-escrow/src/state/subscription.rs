@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+
+/// A pre-funded recurring payment: `taker` may claim `amount_per_period` of `mint` once per
+/// `period` seconds from `starts_at`, for `periods` periods.
+#[account]
+#[derive(InitSpace)]
+pub struct Subscription {
+    pub seed: [u8; 32],
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint: Pubkey,
+    pub amount_per_period: u64,
+    pub period: i64,
+    pub starts_at: i64,
+    pub periods: u32,
+    pub claimed: u32,
+    pub bump: u8,
+}
+
+impl Subscription {
+    /// Number of tranches that have come due by `now`, including already claimed ones.
+    pub fn due(&self, now: i64) -> u32 {
+        if now < self.starts_at {
+            return 0;
+        }
+        let elapsed = ((now - self.starts_at) / self.period).saturating_add(1);
+        elapsed.min(self.periods as i64) as u32
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription() -> Subscription {
+        Subscription {
+            seed: [0; 32],
+            maker: Pubkey::default(),
+            taker: Pubkey::default(),
+            mint: Pubkey::default(),
+            amount_per_period: 1,
+            period: 100,
+            starts_at: 1_000,
+            periods: 3,
+            claimed: 0,
+            bump: 0,
+        }
+    }
+
+    #[test]
+    fn due_counts_each_period_from_the_start() {
+        let subscription = subscription();
+        assert_eq!(subscription.due(999), 0);
+        assert_eq!(subscription.due(1_000), 1);
+        assert_eq!(subscription.due(1_099), 1);
+        assert_eq!(subscription.due(1_100), 2);
+    }
+
+    #[test]
+    fn due_stops_at_the_last_period() {
+        let subscription = subscription();
+        assert_eq!(subscription.due(1_200), 3);
+        assert_eq!(subscription.due(i64::MAX), 3);
+    }
+}
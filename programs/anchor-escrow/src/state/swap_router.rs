@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+
+/// Swap program the admin allows `take_with_route` to invoke.
+#[account]
+#[derive(InitSpace)]
+pub struct SwapRouter {
+    pub program: Pubkey,
+    pub bump: u8,
+}
@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    mul_div, CredentialGate, EscrowError, EscrowParams, PaymentOption, PayoutSplit, PriceTier,
+    Rounding, TakeCondition, MAX_ALLOWED_TAKERS, MAX_PAYMENT_OPTIONS, MAX_PAYOUT_SPLITS,
+    MAX_PRICE_TIERS,
+};
+
+/// Standard terms that anyone can post an escrow from with `make_from_template`.
+#[account]
+#[derive(InitSpace)]
+pub struct Template {
+    pub owner: Pubkey,
+    pub id: u32,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    /// Raw mint_b units asked per `price_denominator` raw mint_a units deposited.
+    pub price_numerator: u64,
+    pub price_denominator: u64,
+    /// Seconds from creation until escrows made from this template expire; zero if they never do.
+    pub duration: i64,
+    pub allow_partial_fills: bool,
+    pub unwrap_sol: bool,
+    pub bump: u8,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct TemplateParams {
+    pub price_numerator: u64,
+    pub price_denominator: u64,
+    pub duration: i64,
+    pub allow_partial_fills: bool,
+    pub unwrap_sol: bool,
+}
+
+impl TemplateParams {
+    pub fn validate(&self) -> Result<()> {
+        require!(self.price_denominator > 0, EscrowError::InvalidPrice);
+        require!(self.duration >= 0, EscrowError::InvalidDuration);
+        Ok(())
+    }
+}
+
+impl Template {
+    /// Price of `deposit` at the template's ratio, rounded up in the maker's favour.
+    pub fn receive_for(&self, deposit: u64) -> Result<u64> {
+        mul_div(
+            deposit,
+            self.price_numerator,
+            self.price_denominator,
+            Rounding::Up,
+        )
+    }
+
+    pub fn params(&self, now: i64) -> EscrowParams {
+        EscrowParams {
+            note: [0; 64],
+            metadata_uri: [0; 128],
+            locked_until: 0,
+            starts_at: 0,
+            expires_at: if self.duration == 0 {
+                0
+            } else {
+                now.saturating_add(self.duration)
+            },
+            allow_permanent_delegate: false,
+            unwrap_sol: self.unwrap_sol,
+            payment_options: [PaymentOption::default(); MAX_PAYMENT_OPTIONS],
+            allow_partial_fills: self.allow_partial_fills,
+            price_tiers: [PriceTier::default(); MAX_PRICE_TIERS],
+            stream_duration: 0,
+            delegate: Pubkey::default(),
+            reject_cpi: false,
+            allowed_takers: [Pubkey::default(); MAX_ALLOWED_TAKERS],
+            sealed_bids: false,
+            payout_splits: [PayoutSplit::default(); MAX_PAYOUT_SPLITS],
+            donation_recipient: Pubkey::default(),
+            donation_bps: 0,
+            relist_receive: 0,
+            condition: TakeCondition::default(),
+            tranche: 0,
+            fill_interval: 0,
+            co_signer: Pubkey::default(),
+            require_compliance: false,
+            credential: CredentialGate::default(),
+            frontend: Pubkey::default(),
+            frontend_fee_bps: 0,
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+pub const TRADE_LOG_LEN: usize = 32;
+
+/// The last `TRADE_LOG_LEN` fills of a pair, oldest overwritten first.
+#[account(zero_copy)]
+pub struct TradeLog {
+    pub trades: [Trade; TRADE_LOG_LEN],
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    /// Slot the next fill is written to.
+    pub head: u32,
+    /// Number of slots written, up to `TRADE_LOG_LEN`.
+    pub len: u32,
+    pub bump: u8,
+    pub _padding: [u8; 7],
+}
+
+#[zero_copy]
+pub struct Trade {
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    /// Raw mint_a units filled.
+    pub amount: u64,
+    /// Raw mint_b units paid.
+    pub receive: u64,
+    pub slot: u64,
+}
+
+impl TradeLog {
+    pub fn push(&mut self, trade: Trade) {
+        self.trades[self.head as usize] = trade;
+        self.head = (self.head + 1) % TRADE_LOG_LEN as u32;
+        self.len = (self.len + 1).min(TRADE_LOG_LEN as u32);
+    }
+}
@@ -0,0 +1,159 @@
+use anchor_lang::{
+    prelude::*,
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        program::invoke_signed,
+        pubkey,
+    },
+    system_program::{transfer, Transfer},
+};
+
+use crate::EscrowError;
+
+/// Wormhole core bridge on mainnet.
+pub const WORMHOLE_PROGRAM_ID: Pubkey = pubkey!("worm2ZoG2kUd4vFXhvjh93UUH596ayRfgQ2MgjNMTth");
+
+/// Core bridge `PostMessage` instruction index.
+const POST_MESSAGE: u8 = 1;
+
+/// Offset of the message fee in the core bridge's config account.
+const BRIDGE_FEE_OFFSET: usize = 16;
+
+/// Wait for finality before guardians sign the message.
+const CONSISTENCY_FINALIZED: u8 = 1;
+
+/// Payload of the message posted when an escrow is taken.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct SettlementMessage {
+    pub escrow: Pubkey,
+    pub maker: Pubkey,
+    pub taker: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub amount: u64,
+    pub receive: u64,
+}
+
+/// Accounts for posting a message, passed as remaining accounts in this order.
+pub struct WormholeAccounts<'a, 'info> {
+    pub program: &'a AccountInfo<'info>,
+    pub bridge: &'a AccountInfo<'info>,
+    /// PDA `[b"wormhole_message", escrow]`, created by the bridge.
+    pub message: &'a AccountInfo<'info>,
+    /// PDA `[b"emitter"]`, the emitter address counterparties verify.
+    pub emitter: &'a AccountInfo<'info>,
+    pub sequence: &'a AccountInfo<'info>,
+    pub fee_collector: &'a AccountInfo<'info>,
+    pub clock: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> WormholeAccounts<'a, 'info> {
+    pub fn from_remaining(accounts: &'a [AccountInfo<'info>]) -> Result<Self> {
+        let [program, bridge, message, emitter, sequence, fee_collector, clock, rent, ..] =
+            accounts
+        else {
+            return err!(EscrowError::MissingWormholeAccount);
+        };
+        require_keys_eq!(
+            *program.key,
+            WORMHOLE_PROGRAM_ID,
+            EscrowError::MissingWormholeAccount
+        );
+
+        Ok(Self {
+            program,
+            bridge,
+            message,
+            emitter,
+            sequence,
+            fee_collector,
+            clock,
+            rent,
+        })
+    }
+
+    /// Pays the bridge fee from `payer` and posts `payload` from the program's emitter.
+    pub fn post_message(
+        &self,
+        escrow: &Pubkey,
+        payload: &SettlementMessage,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+    ) -> Result<()> {
+        let (message, message_bump) =
+            Pubkey::find_program_address(&[b"wormhole_message", escrow.as_ref()], &crate::ID);
+        let (emitter, emitter_bump) = Pubkey::find_program_address(&[b"emitter"], &crate::ID);
+        require_keys_eq!(
+            *self.message.key,
+            message,
+            EscrowError::MissingWormholeAccount
+        );
+        require_keys_eq!(
+            *self.emitter.key,
+            emitter,
+            EscrowError::MissingWormholeAccount
+        );
+
+        let fee = {
+            let data = self.bridge.try_borrow_data()?;
+            data.get(BRIDGE_FEE_OFFSET..BRIDGE_FEE_OFFSET + 8)
+                .map(|bytes| u64::from_le_bytes(bytes.try_into().unwrap()))
+                .ok_or(EscrowError::MissingWormholeAccount)?
+        };
+
+        if fee > 0 {
+            let transfer_accounts = Transfer {
+                from: payer.clone(),
+                to: self.fee_collector.clone(),
+            };
+
+            transfer(
+                CpiContext::new(system_program.clone(), transfer_accounts),
+                fee,
+            )?;
+        }
+
+        let mut data = vec![POST_MESSAGE];
+        0u32.serialize(&mut data)?;
+        payload.try_to_vec()?.serialize(&mut data)?;
+        CONSISTENCY_FINALIZED.serialize(&mut data)?;
+
+        let ix = Instruction {
+            program_id: WORMHOLE_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(*self.bridge.key, false),
+                AccountMeta::new(message, true),
+                AccountMeta::new_readonly(emitter, true),
+                AccountMeta::new(*self.sequence.key, false),
+                AccountMeta::new(*payer.key, true),
+                AccountMeta::new(*self.fee_collector.key, false),
+                AccountMeta::new_readonly(*self.clock.key, false),
+                AccountMeta::new_readonly(*self.rent.key, false),
+                AccountMeta::new_readonly(*system_program.key, false),
+            ],
+            data,
+        };
+
+        invoke_signed(
+            &ix,
+            &[
+                self.bridge.clone(),
+                self.message.clone(),
+                self.emitter.clone(),
+                self.sequence.clone(),
+                payer.clone(),
+                self.fee_collector.clone(),
+                self.clock.clone(),
+                self.rent.clone(),
+                system_program.clone(),
+                self.program.clone(),
+            ],
+            &[
+                &[b"wormhole_message", escrow.as_ref(), &[message_bump]],
+                &[b"emitter", &[emitter_bump]],
+            ],
+        )?;
+        Ok(())
+    }
+}
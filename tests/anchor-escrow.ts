@@ -48,7 +48,48 @@ describe("anchor-escrow", () => {
     return signature;
   };
 
-  const seed = new BN(randomBytes(8));
+  const seed = randomBytes(32);
+
+  const params = {
+    note: Array.from(Buffer.alloc(64)),
+    metadataUri: Array.from(Buffer.alloc(128)),
+    lockedUntil: new BN(0),
+    startsAt: new BN(0),
+    expiresAt: new BN(0),
+    allowPermanentDelegate: false,
+    unwrapSol: false,
+    paymentOptions: Array.from({ length: 3 }, () => ({ mint: PublicKey.default, receive: new BN(0) })),
+    allowPartialFills: false,
+    priceTiers: Array.from({ length: 4 }, () => ({ minFill: new BN(0), discountBps: 0 })),
+    streamDuration: new BN(0),
+    delegate: PublicKey.default,
+    rejectCpi: false,
+    allowedTakers: Array.from({ length: 8 }, () => PublicKey.default),
+    sealedBids: false,
+    payoutSplits: Array.from({ length: 4 }, () => ({ recipient: PublicKey.default, shareBps: 0 })),
+    donationRecipient: PublicKey.default,
+    donationBps: 0,
+    relistReceive: new BN(0),
+    condition: {
+      account: PublicKey.default,
+      offset: 0,
+      size: 0,
+      comparison: { equal: {} },
+      value: new BN(0),
+    },
+    tranche: new BN(0),
+    fillInterval: new BN(0),
+    coSigner: PublicKey.default,
+    requireCompliance: false,
+    credential: {
+      program: PublicKey.default,
+      ownerOffset: 0,
+      issuer: PublicKey.default,
+      issuerOffset: 0,
+    },
+    frontend: PublicKey.default,
+    frontendFeeBps: 0,
+  };
 
   const [maker, taker, mintA, mintB] = Array.from({ length: 4 }, () =>
     Keypair.generate()
@@ -63,12 +104,29 @@ describe("anchor-escrow", () => {
     .flat();
 
   const escrow = PublicKey.findProgramAddressSync(
-    [Buffer.from("escrow"), maker.publicKey.toBuffer(), seed.toArrayLike(Buffer, "le", 8)],
+    [Buffer.from("escrow"), maker.publicKey.toBuffer(), seed],
     program.programId
   )[0];
 
   const vault = getAssociatedTokenAddressSync(mintA.publicKey, escrow, true, tokenProgram);
 
+  const config = PublicKey.findProgramAddressSync(
+    [Buffer.from("config")],
+    program.programId
+  )[0];
+
+  const programData = PublicKey.findProgramAddressSync(
+    [program.programId.toBuffer()],
+    new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+  )[0];
+
+  const treasury = provider.publicKey;
+
+  const pairStats = PublicKey.findProgramAddressSync(
+    [Buffer.from("pair_stats"), mintA.publicKey.toBuffer(), mintB.publicKey.toBuffer()],
+    program.programId
+  )[0];
+
   // Accounts
   const accounts = {
     maker: maker.publicKey,
@@ -81,6 +139,9 @@ describe("anchor-escrow", () => {
     takerAtaB,
     escrow,
     vault,
+    config,
+    treasury,
+    pairStats,
     tokenProgram,
   }
 
@@ -118,20 +179,54 @@ describe("anchor-escrow", () => {
     await provider.sendAndConfirm(tx, [mintA, mintB, maker, taker]).then(log);
   });
 
-  it("Make", async () => {
+  it("Initialize config", async () => {
     await program.methods
-      .make(seed, new BN(1e6), new BN(1e6))
-      .accounts({ ...accounts })
-      .signers([maker])
+      .initializeConfig({
+        treasury,
+        creationFee: new BN(0),
+        cancellationFee: new BN(0),
+        cancellationFeeBps: 0,
+        maxDuration: new BN(0),
+        minDeposit: new BN(0),
+        maxReceive: new BN(0),
+        takeFeeBps: 0,
+        protocolMint: PublicKey.default,
+        protocolFeeDiscountBps: 0,
+        burnProtocolFee: false,
+        referralShareBps: 0,
+        timelock: new BN(0),
+        adminCpiOnly: false,
+        rejectFreezableMints: false,
+        matchIncentiveBps: 0,
+        autoRefundTip: new BN(0),
+        maxCreationsPerEpoch: 0,
+        minDepositTokens: new BN(0),
+        dustThreshold: new BN(0),
+        cpiAllowlist: Array(4).fill(PublicKey.default),
+        complianceSigner: PublicKey.default,
+        maxFrontendFeeBps: 0,
+        insurancePremiumBps: 0,
+        stakeTiers: Array(2).fill({ minStake: new BN(0), discountBps: 0 }),
+      })
+      .accounts({ admin: provider.publicKey, config, program: program.programId, programData })
       .rpc()
       .then(confirm)
       .then(log);
   });
 
-  xit("Refund", async () => {
+  it("Initialize pair stats", async () => {
     await program.methods
-      .refund()
-      .accounts({ ...accounts })
+      .initPairStats()
+      .accounts({ payer: provider.publicKey, ...accounts })
+      .rpc()
+      .then(confirm)
+      .then(log);
+  });
+
+  it("Make", async () => {
+    await program.methods
+      .make(Array.from(seed), new BN(1e6), new BN(1e6), params)
+      .accounts({ ...accounts, payer: maker.publicKey })
       .signers([maker])
       .rpc()
       .then(confirm)
@@ -141,8 +236,8 @@ describe("anchor-escrow", () => {
   it("Take", async () => {
     try {
     await program.methods
-      .take()
-      .accounts({  ...accounts })
+      .take(0)
+      .accounts({ ...accounts, payer: taker.publicKey })
       .signers([taker])
       .rpc()
       .then(confirm)
@@ -152,4 +247,70 @@ describe("anchor-escrow", () => {
       throw(e)
     }
   });
+
+  const refundSeed = randomBytes(32);
+
+  const refundEscrow = PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), maker.publicKey.toBuffer(), refundSeed],
+    program.programId
+  )[0];
+
+  const refundVault = getAssociatedTokenAddressSync(
+    mintA.publicKey,
+    refundEscrow,
+    true,
+    tokenProgram
+  );
+
+  it("Make to refund", async () => {
+    await program.methods
+      .make(Array.from(refundSeed), new BN(1e6), new BN(1e6), params)
+      .accounts({ ...accounts, payer: maker.publicKey, escrow: refundEscrow, vault: refundVault })
+      .signers([maker])
+      .rpc()
+      .then(confirm)
+      .then(log);
+  });
+
+  it("Refund", async () => {
+    await program.methods
+      .refund()
+      .accounts({
+        ...accounts,
+        authority: maker.publicKey,
+        escrow: refundEscrow,
+        vault: refundVault,
+      })
+      .signers([maker])
+      .rpc()
+      .then(confirm)
+      .then(log);
+  });
+
+  const delegatedSeed = randomBytes(32);
+
+  const delegatedEscrow = PublicKey.findProgramAddressSync(
+    [Buffer.from("escrow"), maker.publicKey.toBuffer(), delegatedSeed],
+    program.programId
+  )[0];
+
+  it("Make delegated", async () => {
+    await program.methods
+      .makeDelegated(Array.from(delegatedSeed), new BN(1e6), new BN(1e6), params)
+      .accounts({ ...accounts, payer: maker.publicKey, escrow: delegatedEscrow })
+      .signers([maker])
+      .rpc()
+      .then(confirm)
+      .then(log);
+  });
+
+  it("Take delegated", async () => {
+    await program.methods
+      .takeDelegated()
+      .accounts({ ...accounts, payer: taker.publicKey, escrow: delegatedEscrow })
+      .signers([taker])
+      .rpc()
+      .then(confirm)
+      .then(log);
+  });
 });
@@ -0,0 +1,461 @@
+//! LiteSVM harness for the escrow program.
+//!
+//! The program is loaded from the SBF build in `target/deploy`, so the integration tests are
+//! ignored by default: run `anchor build`, then `cargo test -- --ignored`. Without the build they
+//! fail rather than pass vacuously.
+
+use std::path::Path;
+
+use anchor_escrow::{
+    Config, CredentialGate, Escrow, EscrowParams, PaymentOption, PayoutSplit, PriceTier,
+    TakeCondition, MAX_ALLOWED_TAKERS, MAX_PAYMENT_OPTIONS, MAX_PAYOUT_SPLITS, MAX_PRICE_TIERS,
+};
+use anchor_lang::{
+    prelude::Pubkey, solana_program::program_pack::Pack, AccountDeserialize, AccountSerialize,
+    Discriminator, InstructionData, Space, ToAccountMetas,
+};
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_2022::spl_token_2022::{
+        self,
+        extension::StateWithExtensions,
+        instruction::{initialize_mint2, mint_to, transfer_checked},
+    },
+};
+use litesvm::{types::TransactionMetadata, LiteSVM};
+use solana_sdk::{
+    account::Account,
+    instruction::Instruction,
+    signature::{Keypair, Signer},
+    system_instruction, system_program,
+    transaction::{Transaction, TransactionError},
+};
+use spl_associated_token_account::instruction::create_associated_token_account_idempotent;
+
+pub const PROGRAM_SO: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/../../target/deploy/anchor_escrow.so"
+);
+
+pub const DECIMALS: u8 = 6;
+
+pub struct Harness {
+    pub svm: LiteSVM,
+    pub maker: Keypair,
+    pub taker: Keypair,
+    pub treasury: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub token_program: Pubkey,
+}
+
+impl Harness {
+    /// A fresh SVM with the program, a fee-free config and two funded users: the maker holds
+    /// mint_a and the taker mint_b, both owned by `token_program`. Panics if the program hasn't
+    /// been built.
+    pub fn new(token_program: Pubkey) -> Self {
+        assert!(
+            Path::new(PROGRAM_SO).exists(),
+            "{PROGRAM_SO} not found, run `anchor build` first"
+        );
+
+        let mut svm = LiteSVM::new();
+        svm.add_program_from_file(anchor_escrow::ID, PROGRAM_SO)
+            .expect("load program");
+
+        let maker = Keypair::new();
+        let taker = Keypair::new();
+        let treasury = Pubkey::new_unique();
+        for user in [&maker, &taker] {
+            svm.airdrop(&user.pubkey(), 10_000_000_000)
+                .expect("airdrop");
+        }
+
+        let mut harness = Self {
+            svm,
+            maker,
+            taker,
+            treasury,
+            mint_a: Pubkey::default(),
+            mint_b: Pubkey::default(),
+            token_program,
+        };
+        harness.write_config();
+        harness.mint_a = harness.create_mint(&harness.maker.insecure_clone(), 1_000_000_000);
+        harness.mint_b = harness.create_mint(&harness.taker.insecure_clone(), 1_000_000_000);
+        harness
+    }
+
+    /// Writes the config account directly, since `initialize_config` needs an upgradeable
+    /// program. Every field left at zero disables its fee or cap.
+    fn write_config(&mut self) {
+        let address = config_address();
+        let mut config =
+            Config::try_deserialize_unchecked(&mut &vec![0; 8 + Config::INIT_SPACE][..]).unwrap();
+        config.admin = self.treasury;
+        config.treasury = self.treasury;
+        config.bump = Pubkey::find_program_address(&[b"config"], &anchor_escrow::ID).1;
+
+        let mut data = Vec::with_capacity(8 + Config::INIT_SPACE);
+        config.try_serialize(&mut data).unwrap();
+        self.set_program_account(address, data);
+    }
+
+    fn set_program_account(&mut self, address: Pubkey, data: Vec<u8>) {
+        let account = Account {
+            lamports: self.svm.minimum_balance_for_rent_exemption(data.len()),
+            data,
+            owner: anchor_escrow::ID,
+            executable: false,
+            rent_epoch: 0,
+        };
+        self.svm.set_account(address, account).unwrap();
+    }
+
+    /// Creates a mint with `authority` and mints `supply` into the authority's ATA.
+    fn create_mint(&mut self, authority: &Keypair, supply: u64) -> Pubkey {
+        let mint = Keypair::new();
+        let space = spl_token_2022::state::Mint::LEN;
+        let ata = self.ata(&authority.pubkey(), &mint.pubkey());
+        let instructions = [
+            system_instruction::create_account(
+                &authority.pubkey(),
+                &mint.pubkey(),
+                self.svm.minimum_balance_for_rent_exemption(space),
+                space as u64,
+                &self.token_program,
+            ),
+            initialize_mint2(
+                &self.token_program,
+                &mint.pubkey(),
+                &authority.pubkey(),
+                None,
+                DECIMALS,
+            )
+            .unwrap(),
+            create_associated_token_account_idempotent(
+                &authority.pubkey(),
+                &authority.pubkey(),
+                &mint.pubkey(),
+                &self.token_program,
+            ),
+            mint_to(
+                &self.token_program,
+                &mint.pubkey(),
+                &ata,
+                &authority.pubkey(),
+                &[],
+                supply,
+            )
+            .unwrap(),
+        ];
+        self.send(&instructions, &[authority, &mint])
+            .expect("create mint");
+        mint.pubkey()
+    }
+
+    pub fn ata(&self, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        get_associated_token_address_with_program_id(owner, mint, &self.token_program)
+    }
+
+    /// Raw token balance of `owner`'s ATA for `mint`, or zero if it doesn't exist.
+    pub fn balance(&self, owner: &Pubkey, mint: &Pubkey) -> u64 {
+        self.svm
+            .get_account(&self.ata(owner, mint))
+            .filter(|account| !account.data.is_empty())
+            .map(|account| {
+                StateWithExtensions::<spl_token_2022::state::Account>::unpack(&account.data)
+                    .unwrap()
+                    .base
+                    .amount
+            })
+            .unwrap_or(0)
+    }
+
+    pub fn exists(&self, address: &Pubkey) -> bool {
+        self.svm
+            .get_account(address)
+            .is_some_and(|account| account.lamports > 0)
+    }
+
+    /// Sends `instructions` paid by the first signer, printing the program logs if it fails.
+    pub fn send(
+        &mut self,
+        instructions: &[Instruction],
+        signers: &[&Keypair],
+    ) -> Result<TransactionMetadata, TransactionError> {
+        let tx = Transaction::new_signed_with_payer(
+            instructions,
+            Some(&signers[0].pubkey()),
+            signers,
+            self.svm.latest_blockhash(),
+        );
+        let result = self.svm.send_transaction(tx);
+        self.svm.expire_blockhash();
+        result.map_err(|failed| {
+            failed.meta.logs.iter().for_each(|log| eprintln!("{log}"));
+            failed.err
+        })
+    }
+
+    pub fn escrow_address(&self, seed: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(
+            &[b"escrow", self.maker.pubkey().as_ref(), seed],
+            &anchor_escrow::ID,
+        )
+        .0
+    }
+
+    /// Writes an escrow in the first release's layout, under its u64-seed address, as a
+    /// deployment that predates 32-byte seeds would hold it.
+    pub fn write_baseline_escrow(&mut self, seed: u64, receive: u64) -> Pubkey {
+        let maker = self.maker.pubkey();
+        let (address, bump) = Pubkey::find_program_address(
+            &[b"escrow", maker.as_ref(), seed.to_le_bytes().as_ref()],
+            &anchor_escrow::ID,
+        );
+
+        let mut data = Vec::with_capacity(Escrow::BASELINE_LEN);
+        data.extend_from_slice(&Escrow::DISCRIMINATOR);
+        data.extend_from_slice(&seed.to_le_bytes());
+        data.extend_from_slice(maker.as_ref());
+        data.extend_from_slice(self.mint_a.as_ref());
+        data.extend_from_slice(self.mint_b.as_ref());
+        data.extend_from_slice(&receive.to_le_bytes());
+        data.push(bump);
+        assert_eq!(data.len(), Escrow::BASELINE_LEN);
+
+        self.set_program_account(address, data);
+        address
+    }
+
+    /// Moves `amount` of the maker's mint_a into `escrow`'s vault, creating it if needed.
+    pub fn fund_vault(&mut self, escrow: &Pubkey, amount: u64) {
+        let maker = self.maker.insecure_clone();
+        let instructions = [
+            create_associated_token_account_idempotent(
+                &maker.pubkey(),
+                escrow,
+                &self.mint_a,
+                &self.token_program,
+            ),
+            transfer_checked(
+                &self.token_program,
+                &self.ata(&maker.pubkey(), &self.mint_a),
+                &self.mint_a,
+                &self.ata(escrow, &self.mint_a),
+                &maker.pubkey(),
+                &[],
+                amount,
+                DECIMALS,
+            )
+            .unwrap(),
+        ];
+        self.send(&instructions, &[&maker]).expect("fund vault");
+    }
+
+    pub fn refund_legacy_ix(&self, escrow: Pubkey) -> Instruction {
+        let maker = self.maker.pubkey();
+        Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::RefundLegacy {
+                maker,
+                mint_a: self.mint_a,
+                maker_ata_a: self.ata(&maker, &self.mint_a),
+                escrow,
+                vault: self.ata(&escrow, &self.mint_a),
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::RefundLegacy {}.data(),
+        }
+    }
+
+    pub fn migrate_escrow_ix(&self, escrow: Pubkey) -> Instruction {
+        Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::MigrateEscrow {
+                payer: self.maker.pubkey(),
+                escrow,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::MigrateEscrow {}.data(),
+        }
+    }
+
+    pub fn make_ix(&self, seed: [u8; 32], deposit: u64, receive: u64) -> Instruction {
+        let escrow = self.escrow_address(&seed);
+        let maker = self.maker.pubkey();
+        Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::Make {
+                maker,
+                payer: maker,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                maker_ata_a: self.ata(&maker, &self.mint_a),
+                config: config_address(),
+                treasury: self.treasury,
+                escrow,
+                vault: self.ata(&escrow, &self.mint_a),
+                maker_counter: None,
+                maker_activity: Pubkey::find_program_address(
+                    &[b"maker_activity", maker.as_ref()],
+                    &anchor_escrow::ID,
+                )
+                .0,
+                maker_denylist_entry: denylist_address(&maker),
+                maker_directory: None,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::Make {
+                seed,
+                deposit,
+                receive,
+                params: default_params(),
+            }
+            .data(),
+        }
+    }
+
+    pub fn take_ix(&self, seed: [u8; 32]) -> Instruction {
+        let escrow = self.escrow_address(&seed);
+        let maker = self.maker.pubkey();
+        let taker = self.taker.pubkey();
+        Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::Take {
+                taker,
+                payer: taker,
+                session_signer: None,
+                session_token: None,
+                co_signer: None,
+                compliance_signer: None,
+                credential: None,
+                maker,
+                taker_denylist_entry: denylist_address(&taker),
+                maker_denylist_entry: denylist_address(&maker),
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                taker_ata_a: self.ata(&taker, &self.mint_a),
+                taker_ata_b: Some(self.ata(&taker, &self.mint_b)),
+                taker_wsol: None,
+                maker_ata_b: self.ata(&maker, &self.mint_b),
+                escrow,
+                vault: self.ata(&escrow, &self.mint_a),
+                config: config_address(),
+                fee_override: None,
+                treasury_ata_b: None,
+                referrer_ata_b: None,
+                affiliate: None,
+                affiliate_vault: None,
+                protocol_fee_rate: None,
+                protocol_mint: None,
+                taker_protocol_ata: None,
+                treasury_protocol_ata: None,
+                protocol_token_program: None,
+                pair_stats: None,
+                maker_directory: None,
+                trade_log: None,
+                donation_ata_b: None,
+                frontend_ata_b: None,
+                insurance_pool: None,
+                insurance_vault: None,
+                fee_stake: None,
+                taker_rebate: None,
+                rebate_vault: None,
+                rebate_schedule: None,
+                fee_epoch: None,
+                taker_points: None,
+                leaderboard: None,
+                points_rate: None,
+                maker_points: None,
+                maker_points_rate: None,
+                relist_escrow: None,
+                relist_vault: None,
+                memo_program: None,
+                maker_wsol: None,
+                associated_token_program: anchor_spl::associated_token::ID,
+                instructions: None,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::Take { _payment_index: 0 }.data(),
+        }
+    }
+
+    pub fn refund_ix(&self, seed: [u8; 32]) -> Instruction {
+        let escrow = self.escrow_address(&seed);
+        let maker = self.maker.pubkey();
+        Instruction {
+            program_id: anchor_escrow::ID,
+            accounts: anchor_escrow::accounts::Refund {
+                authority: maker,
+                maker,
+                mint_a: self.mint_a,
+                mint_b: self.mint_b,
+                maker_ata_a: self.ata(&maker, &self.mint_a),
+                escrow,
+                vault: self.ata(&escrow, &self.mint_a),
+                config: config_address(),
+                treasury: self.treasury,
+                treasury_ata_a: None,
+                maker_directory: None,
+                memo_program: None,
+                associated_token_program: anchor_spl::associated_token::ID,
+                token_program: self.token_program,
+                system_program: system_program::ID,
+            }
+            .to_account_metas(None),
+            data: anchor_escrow::instruction::Refund {}.data(),
+        }
+    }
+}
+
+pub fn config_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"config"], &anchor_escrow::ID).0
+}
+
+pub fn denylist_address(address: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"denylist", address.as_ref()], &anchor_escrow::ID).0
+}
+
+/// Terms with every optional feature switched off.
+pub fn default_params() -> EscrowParams {
+    EscrowParams {
+        note: [0; 64],
+        metadata_uri: [0; 128],
+        locked_until: 0,
+        starts_at: 0,
+        expires_at: 0,
+        allow_permanent_delegate: false,
+        unwrap_sol: false,
+        payment_options: [PaymentOption::default(); MAX_PAYMENT_OPTIONS],
+        allow_partial_fills: false,
+        price_tiers: [PriceTier::default(); MAX_PRICE_TIERS],
+        stream_duration: 0,
+        delegate: Pubkey::default(),
+        reject_cpi: false,
+        allowed_takers: [Pubkey::default(); MAX_ALLOWED_TAKERS],
+        sealed_bids: false,
+        payout_splits: [PayoutSplit::default(); MAX_PAYOUT_SPLITS],
+        donation_recipient: Pubkey::default(),
+        donation_bps: 0,
+        relist_receive: 0,
+        condition: TakeCondition::default(),
+        tranche: 0,
+        fill_interval: 0,
+        co_signer: Pubkey::default(),
+        require_compliance: false,
+        credential: CredentialGate::default(),
+        frontend: Pubkey::default(),
+        frontend_fee_bps: 0,
+    }
+}
@@ -0,0 +1,156 @@
+use anchor_escrow_integration::Harness;
+use anchor_spl::{token::ID as TOKEN_PROGRAM_ID, token_2022::ID as TOKEN_2022_PROGRAM_ID};
+use solana_sdk::signature::Signer;
+
+/// Upper bounds on compute units per transaction, CPIs included. Tighten them when an
+/// optimisation lands and raise them only deliberately.
+const MAKE_CU_BUDGET: u64 = 100_000;
+const TAKE_CU_BUDGET: u64 = 120_000;
+const REFUND_CU_BUDGET: u64 = 80_000;
+
+const DEPOSIT: u64 = 1_000_000;
+const RECEIVE: u64 = 2_000_000;
+
+fn make_then_take(token_program: solana_sdk::pubkey::Pubkey) {
+    let mut harness = Harness::new(token_program);
+    let seed = [1; 32];
+    let escrow = harness.escrow_address(&seed);
+    let (maker, taker) = (harness.maker.pubkey(), harness.taker.pubkey());
+    let (mint_a, mint_b) = (harness.mint_a, harness.mint_b);
+
+    let maker_kp = harness.maker.insecure_clone();
+    let make = harness
+        .send(&[harness.make_ix(seed, DEPOSIT, RECEIVE)], &[&maker_kp])
+        .expect("make");
+    assert!(
+        make.compute_units_consumed <= MAKE_CU_BUDGET,
+        "make used {}",
+        make.compute_units_consumed
+    );
+    assert_eq!(harness.balance(&escrow, &mint_a), DEPOSIT);
+
+    let taker_kp = harness.taker.insecure_clone();
+    let take = harness
+        .send(&[harness.take_ix(seed)], &[&taker_kp])
+        .expect("take");
+    assert!(
+        take.compute_units_consumed <= TAKE_CU_BUDGET,
+        "take used {}",
+        take.compute_units_consumed
+    );
+    assert_eq!(harness.balance(&taker, &mint_a), DEPOSIT);
+    assert_eq!(harness.balance(&maker, &mint_b), RECEIVE);
+    assert!(!harness.exists(&escrow));
+    assert!(!harness.exists(&harness.ata(&escrow, &mint_a)));
+
+    // The escrow is gone, so a second take fails.
+    assert!(harness
+        .send(&[harness.take_ix(seed)], &[&taker_kp])
+        .is_err());
+}
+
+fn make_then_refund(token_program: solana_sdk::pubkey::Pubkey) {
+    let mut harness = Harness::new(token_program);
+    let seed = [2; 32];
+    let escrow = harness.escrow_address(&seed);
+    let maker = harness.maker.pubkey();
+    let mint_a = harness.mint_a;
+    let before = harness.balance(&maker, &mint_a);
+
+    let maker_kp = harness.maker.insecure_clone();
+    harness
+        .send(&[harness.make_ix(seed, DEPOSIT, RECEIVE)], &[&maker_kp])
+        .expect("make");
+
+    // Only the maker or their delegate may refund.
+    let taker_kp = harness.taker.insecure_clone();
+    let mut stolen = harness.refund_ix(seed);
+    stolen.accounts[0].pubkey = taker_kp.pubkey();
+    assert!(harness.send(&[stolen], &[&taker_kp]).is_err());
+
+    let refund = harness
+        .send(&[harness.refund_ix(seed)], &[&maker_kp])
+        .expect("refund");
+    assert!(
+        refund.compute_units_consumed <= REFUND_CU_BUDGET,
+        "refund used {}",
+        refund.compute_units_consumed
+    );
+    assert_eq!(harness.balance(&maker, &mint_a), before);
+    assert!(!harness.exists(&escrow));
+}
+
+fn migrate_baseline_escrow(token_program: solana_sdk::pubkey::Pubkey) {
+    let mut harness = Harness::new(token_program);
+    let escrow = harness.write_baseline_escrow(7, RECEIVE);
+    let before = harness.svm.get_account(&escrow).expect("baseline escrow");
+
+    // A first-release escrow doesn't match any migratable layout, so it is left as it was
+    // rather than reinterpreted as the current one.
+    let maker_kp = harness.maker.insecure_clone();
+    assert!(harness
+        .send(&[harness.migrate_escrow_ix(escrow)], &[&maker_kp])
+        .is_err());
+    assert_eq!(harness.svm.get_account(&escrow), Some(before));
+}
+
+fn refund_baseline_escrow(token_program: solana_sdk::pubkey::Pubkey) {
+    let mut harness = Harness::new(token_program);
+    let maker = harness.maker.pubkey();
+    let mint_a = harness.mint_a;
+    let before = harness.balance(&maker, &mint_a);
+    let escrow = harness.write_baseline_escrow(7, RECEIVE);
+    harness.fund_vault(&escrow, DEPOSIT);
+    assert_eq!(harness.balance(&maker, &mint_a), before - DEPOSIT);
+
+    let maker_kp = harness.maker.insecure_clone();
+    harness
+        .send(&[harness.refund_legacy_ix(escrow)], &[&maker_kp])
+        .expect("refund_legacy");
+
+    assert_eq!(harness.balance(&maker, &mint_a), before);
+    assert!(!harness.exists(&escrow));
+    assert!(!harness.exists(&harness.ata(&escrow, &mint_a)));
+}
+
+#[test]
+#[ignore = "needs the SBF build from `anchor build`"]
+fn make_and_take_token() {
+    make_then_take(TOKEN_PROGRAM_ID);
+}
+
+#[test]
+#[ignore = "needs the SBF build from `anchor build`"]
+fn make_and_take_token_2022() {
+    make_then_take(TOKEN_2022_PROGRAM_ID);
+}
+
+#[test]
+#[ignore = "needs the SBF build from `anchor build`"]
+fn make_and_refund_token() {
+    make_then_refund(TOKEN_PROGRAM_ID);
+}
+
+#[test]
+#[ignore = "needs the SBF build from `anchor build`"]
+fn make_and_refund_token_2022() {
+    make_then_refund(TOKEN_2022_PROGRAM_ID);
+}
+
+#[test]
+#[ignore = "needs the SBF build from `anchor build`"]
+fn migrate_rejects_baseline_escrow() {
+    migrate_baseline_escrow(TOKEN_PROGRAM_ID);
+}
+
+#[test]
+#[ignore = "needs the SBF build from `anchor build`"]
+fn refund_legacy_returns_baseline_deposit() {
+    refund_baseline_escrow(TOKEN_PROGRAM_ID);
+}
+
+#[test]
+#[ignore = "needs the SBF build from `anchor build`"]
+fn refund_legacy_returns_baseline_deposit_token_2022() {
+    refund_baseline_escrow(TOKEN_2022_PROGRAM_ID);
+}
@@ -0,0 +1,579 @@
+use anchor_escrow::{
+    Config as EscrowConfig, CredentialGate, Escrow, EscrowParams, PaymentOption, PayoutSplit,
+    PriceTier, TakeCondition, MAX_ALLOWED_TAKERS, MAX_PAYMENT_OPTIONS, MAX_PAYOUT_SPLITS,
+    MAX_PRICE_TIERS,
+};
+use anchor_lang::{AccountSerialize, Discriminator, Space};
+use anchor_spl::{
+    associated_token::get_associated_token_address,
+    token::spl_token::{self, solana_program::program_pack::Pack},
+};
+use trident_client::fuzzing::*;
+
+/// Users, mints and seeds are drawn from pools this small so that the fuzzer keeps reusing, and
+/// mismatching, accounts that already exist.
+const POOL: u8 = 3;
+
+const TREASURY: Pubkey = Pubkey::new_from_array([7; 32]);
+
+/// FuzzInstruction contains all available Instructions.
+/// Below, the instruction arguments (accounts and data) are defined.
+#[derive(Arbitrary, DisplayIx, FuzzTestExecutor)]
+pub enum FuzzInstruction {
+    Make(Make),
+    Take(Take),
+    Refund(Refund),
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct Make {
+    pub accounts: MakeAccounts,
+    pub data: MakeData,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct MakeAccounts {
+    pub maker: AccountId,
+    pub mint_a: AccountId,
+    pub mint_b: AccountId,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct MakeData {
+    pub seed: u8,
+    pub deposit: u64,
+    pub receive: u64,
+    pub locked_until: i64,
+    pub starts_at: i64,
+    pub expires_at: i64,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct Take {
+    pub accounts: TakeAccounts,
+    pub data: TakeData,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct TakeAccounts {
+    pub taker: AccountId,
+    pub maker: AccountId,
+    pub mint_a: AccountId,
+    pub mint_b: AccountId,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct TakeData {
+    pub seed: u8,
+    pub payment_index: u8,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct Refund {
+    pub accounts: RefundAccounts,
+    pub data: RefundData,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct RefundAccounts {
+    pub authority: AccountId,
+    pub maker: AccountId,
+    pub mint_a: AccountId,
+    pub mint_b: AccountId,
+}
+
+#[derive(Arbitrary, Debug)]
+pub struct RefundData {
+    pub seed: u8,
+}
+
+/// IxOps implementation for `Make` with all required functions.
+impl IxOps for Make {
+    type IxData = anchor_escrow::instruction::Make;
+    type IxAccounts = FuzzAccounts;
+
+    fn get_program_id(&self) -> solana_sdk::pubkey::Pubkey {
+        anchor_escrow::ID
+    }
+
+    fn get_data(
+        &self,
+        _client: &mut impl FuzzClient,
+        _fuzz_accounts: &mut FuzzAccounts,
+    ) -> Result<Self::IxData, FuzzingError> {
+        let mut params = default_params();
+        params.locked_until = self.data.locked_until;
+        params.starts_at = self.data.starts_at;
+        params.expires_at = self.data.expires_at;
+        Ok(anchor_escrow::instruction::Make {
+            seed: seed(self.data.seed),
+            deposit: self.data.deposit,
+            receive: self.data.receive,
+            params,
+        })
+    }
+
+    fn get_accounts(
+        &self,
+        client: &mut impl FuzzClient,
+        fuzz_accounts: &mut FuzzAccounts,
+    ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+        let config = fuzz_accounts.config(client);
+        let maker = fuzz_accounts.user(self.accounts.maker, client);
+        let mint_a = fuzz_accounts.mint(self.accounts.mint_a, client);
+        let mint_b = fuzz_accounts.mint(self.accounts.mint_b, client);
+        let escrow = escrow_address(&maker.pubkey(), self.data.seed);
+        let acc_meta = anchor_escrow::accounts::Make {
+            maker: maker.pubkey(),
+            payer: maker.pubkey(),
+            mint_a,
+            mint_b,
+            maker_ata_a: fuzz_accounts.ata(client, &maker.pubkey(), &mint_a),
+            config,
+            treasury: TREASURY,
+            escrow,
+            vault: get_associated_token_address(&escrow, &mint_a),
+            maker_counter: None,
+            maker_activity: Pubkey::find_program_address(
+                &[b"maker_activity", maker.pubkey().as_ref()],
+                &anchor_escrow::ID,
+            )
+            .0,
+            maker_denylist_entry: denylist_address(&maker.pubkey()),
+            maker_directory: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: spl_token::ID,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None);
+        Ok((vec![maker], acc_meta))
+    }
+
+    fn check(
+        &self,
+        _pre_ix: &[SnapshotAccount],
+        post_ix: &[SnapshotAccount],
+        ix_data: Self::IxData,
+    ) -> Result<(), FuzzingError> {
+        let (escrow_key, escrow) = find_escrow(post_ix).ok_or(FuzzingError::Custom(1))?;
+        let vault = token_accounts(post_ix)
+            .find(|(_, account)| account.owner == escrow_key)
+            .ok_or(FuzzingError::Custom(2))?;
+        if escrow.amount != ix_data.deposit
+            || escrow.receive != ix_data.receive
+            || escrow.vault != vault.0
+            || vault.1.mint != escrow.mint_a
+            || vault.1.amount != ix_data.deposit
+        {
+            return Err(FuzzingError::Custom(3));
+        }
+        Ok(())
+    }
+
+    fn tx_error_handler(
+        &self,
+        _e: FuzzClientErrorWithOrigin,
+        _ix_data: Self::IxData,
+        _pre_ix_acc_infos: &[SnapshotAccount],
+    ) -> Result<(), FuzzClientErrorWithOrigin> {
+        Ok(())
+    }
+}
+
+/// IxOps implementation for `Take` with all required functions.
+impl IxOps for Take {
+    type IxData = anchor_escrow::instruction::Take;
+    type IxAccounts = FuzzAccounts;
+
+    fn get_program_id(&self) -> solana_sdk::pubkey::Pubkey {
+        anchor_escrow::ID
+    }
+
+    fn get_data(
+        &self,
+        _client: &mut impl FuzzClient,
+        _fuzz_accounts: &mut FuzzAccounts,
+    ) -> Result<Self::IxData, FuzzingError> {
+        Ok(anchor_escrow::instruction::Take {
+            // One past the last payment option, so that invalid indices are still exercised.
+            _payment_index: self.data.payment_index % (MAX_PAYMENT_OPTIONS as u8 + 2),
+        })
+    }
+
+    fn get_accounts(
+        &self,
+        client: &mut impl FuzzClient,
+        fuzz_accounts: &mut FuzzAccounts,
+    ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+        let config = fuzz_accounts.config(client);
+        let taker = fuzz_accounts.user(self.accounts.taker, client);
+        let maker = fuzz_accounts.user(self.accounts.maker, client).pubkey();
+        let mint_a = fuzz_accounts.mint(self.accounts.mint_a, client);
+        let mint_b = fuzz_accounts.mint(self.accounts.mint_b, client);
+        let escrow = escrow_address(&maker, self.data.seed);
+        let acc_meta = anchor_escrow::accounts::Take {
+            taker: taker.pubkey(),
+            payer: taker.pubkey(),
+            session_signer: None,
+            session_token: None,
+            co_signer: None,
+            compliance_signer: None,
+            credential: None,
+            maker,
+            taker_denylist_entry: denylist_address(&taker.pubkey()),
+            maker_denylist_entry: denylist_address(&maker),
+            mint_a,
+            mint_b,
+            taker_ata_a: get_associated_token_address(&taker.pubkey(), &mint_a),
+            taker_ata_b: Some(fuzz_accounts.ata(client, &taker.pubkey(), &mint_b)),
+            taker_wsol: None,
+            maker_ata_b: get_associated_token_address(&maker, &mint_b),
+            escrow,
+            vault: get_associated_token_address(&escrow, &mint_a),
+            config,
+            fee_override: None,
+            treasury_ata_b: None,
+            referrer_ata_b: None,
+            affiliate: None,
+            affiliate_vault: None,
+            protocol_fee_rate: None,
+            protocol_mint: None,
+            taker_protocol_ata: None,
+            treasury_protocol_ata: None,
+            protocol_token_program: None,
+            pair_stats: None,
+            maker_directory: None,
+            trade_log: None,
+            donation_ata_b: None,
+            frontend_ata_b: None,
+            insurance_pool: None,
+            insurance_vault: None,
+            fee_stake: None,
+            taker_rebate: None,
+            rebate_vault: None,
+            rebate_schedule: None,
+            fee_epoch: None,
+            taker_points: None,
+            leaderboard: None,
+            points_rate: None,
+            maker_points: None,
+            maker_points_rate: None,
+            relist_escrow: None,
+            relist_vault: None,
+            memo_program: None,
+            maker_wsol: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            instructions: None,
+            token_program: spl_token::ID,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None);
+        Ok((vec![taker], acc_meta))
+    }
+
+    /// A take moves the whole vault to the taker, pays the maker the full price and closes the
+    /// escrow. With the all-zero config there are no fees.
+    fn check(
+        &self,
+        pre_ix: &[SnapshotAccount],
+        post_ix: &[SnapshotAccount],
+        _ix_data: Self::IxData,
+    ) -> Result<(), FuzzingError> {
+        let (escrow_key, escrow) = find_escrow(pre_ix).ok_or(FuzzingError::Custom(1))?;
+        if find_escrow(post_ix).is_some() {
+            return Err(FuzzingError::Custom(2));
+        }
+        // The taker is the first account of `Take`.
+        let taker = pre_ix[0].pubkey();
+        let vault = vault_amount(pre_ix, &escrow_key) as i128;
+        let receive = escrow.receive as i128;
+        let flows = [
+            (taker, escrow.mint_a, vault),
+            (taker, escrow.mint_b, -receive),
+            (escrow.maker, escrow.mint_b, receive),
+        ];
+        if !flows_match(pre_ix, post_ix, &flows) {
+            return Err(FuzzingError::Custom(3));
+        }
+        Ok(())
+    }
+
+    fn tx_error_handler(
+        &self,
+        _e: FuzzClientErrorWithOrigin,
+        _ix_data: Self::IxData,
+        _pre_ix_acc_infos: &[SnapshotAccount],
+    ) -> Result<(), FuzzClientErrorWithOrigin> {
+        Ok(())
+    }
+}
+
+/// IxOps implementation for `Refund` with all required functions.
+impl IxOps for Refund {
+    type IxData = anchor_escrow::instruction::Refund;
+    type IxAccounts = FuzzAccounts;
+
+    fn get_program_id(&self) -> solana_sdk::pubkey::Pubkey {
+        anchor_escrow::ID
+    }
+
+    fn get_data(
+        &self,
+        _client: &mut impl FuzzClient,
+        _fuzz_accounts: &mut FuzzAccounts,
+    ) -> Result<Self::IxData, FuzzingError> {
+        Ok(anchor_escrow::instruction::Refund {})
+    }
+
+    fn get_accounts(
+        &self,
+        client: &mut impl FuzzClient,
+        fuzz_accounts: &mut FuzzAccounts,
+    ) -> Result<(Vec<Keypair>, Vec<AccountMeta>), FuzzingError> {
+        let config = fuzz_accounts.config(client);
+        let authority = fuzz_accounts.user(self.accounts.authority, client);
+        let maker = fuzz_accounts.user(self.accounts.maker, client).pubkey();
+        let mint_a = fuzz_accounts.mint(self.accounts.mint_a, client);
+        let mint_b = fuzz_accounts.mint(self.accounts.mint_b, client);
+        let escrow = escrow_address(&maker, self.data.seed);
+        let acc_meta = anchor_escrow::accounts::Refund {
+            authority: authority.pubkey(),
+            maker,
+            mint_a,
+            mint_b,
+            maker_ata_a: get_associated_token_address(&maker, &mint_a),
+            escrow,
+            vault: get_associated_token_address(&escrow, &mint_a),
+            config,
+            treasury: TREASURY,
+            treasury_ata_a: None,
+            maker_directory: None,
+            memo_program: None,
+            associated_token_program: anchor_spl::associated_token::ID,
+            token_program: spl_token::ID,
+            system_program: solana_sdk::system_program::ID,
+        }
+        .to_account_metas(None);
+        Ok((vec![authority], acc_meta))
+    }
+
+    /// Only the maker may refund (the fuzzed escrows have no delegate), and the whole vault
+    /// returns to the maker.
+    fn check(
+        &self,
+        pre_ix: &[SnapshotAccount],
+        post_ix: &[SnapshotAccount],
+        _ix_data: Self::IxData,
+    ) -> Result<(), FuzzingError> {
+        let (escrow_key, escrow) = find_escrow(pre_ix).ok_or(FuzzingError::Custom(1))?;
+        // The authority is the first account of `Refund`.
+        if pre_ix[0].pubkey() != escrow.maker {
+            return Err(FuzzingError::Custom(2));
+        }
+        let vault = vault_amount(pre_ix, &escrow_key) as i128;
+        if !flows_match(pre_ix, post_ix, &[(escrow.maker, escrow.mint_a, vault)]) {
+            return Err(FuzzingError::Custom(3));
+        }
+        Ok(())
+    }
+
+    fn tx_error_handler(
+        &self,
+        _e: FuzzClientErrorWithOrigin,
+        _ix_data: Self::IxData,
+        _pre_ix_acc_infos: &[SnapshotAccount],
+    ) -> Result<(), FuzzClientErrorWithOrigin> {
+        Ok(())
+    }
+}
+
+/// Check supported AccountsStorages at
+/// https://ackee.xyz/trident/docs/latest/features/account-storages/
+#[derive(Default)]
+pub struct FuzzAccounts {
+    config: AccountsStorage<PdaStore>,
+    mints: AccountsStorage<MintStore>,
+    users: AccountsStorage<KeypairStore>,
+}
+
+impl FuzzAccounts {
+    /// The config PDA, written directly on first use since `initialize_config` needs an
+    /// upgradeable program. Every field left at zero disables its fee or cap.
+    fn config(&mut self, client: &mut impl FuzzClient) -> Pubkey {
+        let (address, bump) = Pubkey::find_program_address(&[b"config"], &anchor_escrow::ID);
+        if client.get_account(&address).data().is_empty() {
+            let mut config = EscrowConfig::try_deserialize_unchecked(
+                &mut &vec![0; 8 + EscrowConfig::INIT_SPACE][..],
+            )
+            .unwrap();
+            config.treasury = TREASURY;
+            config.bump = bump;
+
+            let mut data = Vec::with_capacity(8 + EscrowConfig::INIT_SPACE);
+            config.try_serialize(&mut data).unwrap();
+            let mut account = AccountSharedData::new(
+                solana_sdk::rent::Rent::default().minimum_balance(data.len()),
+                data.len(),
+                &anchor_escrow::ID,
+            );
+            account.set_data_from_slice(&data);
+            self.config.set_custom(0, client, address, account);
+        }
+        address
+    }
+
+    fn user(&mut self, id: AccountId, client: &mut impl FuzzClient) -> Keypair {
+        self.users
+            .get_or_create_account(id % POOL, client, 100 * LAMPORTS_PER_SOL)
+    }
+
+    fn mint(&mut self, id: AccountId, client: &mut impl FuzzClient) -> Pubkey {
+        let authority = Pubkey::new_from_array([9; 32]);
+        self.mints
+            .get_or_create_account(id % POOL, client, 6, &authority, None)
+    }
+
+    /// `owner`'s ATA for `mint`, created with a large balance if it doesn't exist yet.
+    fn ata(&mut self, client: &mut impl FuzzClient, owner: &Pubkey, mint: &Pubkey) -> Pubkey {
+        let address = get_associated_token_address(owner, mint);
+        if client.get_account(&address).data().is_empty() {
+            let state = spl_token::state::Account {
+                mint: *mint,
+                owner: *owner,
+                amount: u64::MAX / 2,
+                state: spl_token::state::AccountState::Initialized,
+                ..Default::default()
+            };
+            let mut data = vec![0; spl_token::state::Account::LEN];
+            spl_token::state::Account::pack(state, &mut data).unwrap();
+            let mut account = AccountSharedData::new(
+                solana_sdk::rent::Rent::default().minimum_balance(data.len()),
+                data.len(),
+                &spl_token::ID,
+            );
+            account.set_data_from_slice(&data);
+            client.set_account_custom(&address, &account);
+        }
+        address
+    }
+}
+
+fn seed(id: u8) -> [u8; 32] {
+    [id % POOL; 32]
+}
+
+fn escrow_address(maker: &Pubkey, id: u8) -> Pubkey {
+    Pubkey::find_program_address(&[b"escrow", maker.as_ref(), &seed(id)], &anchor_escrow::ID).0
+}
+
+fn denylist_address(address: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"denylist", address.as_ref()], &anchor_escrow::ID).0
+}
+
+fn default_params() -> EscrowParams {
+    EscrowParams {
+        note: [0; 64],
+        metadata_uri: [0; 128],
+        locked_until: 0,
+        starts_at: 0,
+        expires_at: 0,
+        allow_permanent_delegate: false,
+        unwrap_sol: false,
+        payment_options: [PaymentOption::default(); MAX_PAYMENT_OPTIONS],
+        allow_partial_fills: false,
+        price_tiers: [PriceTier::default(); MAX_PRICE_TIERS],
+        stream_duration: 0,
+        delegate: Pubkey::default(),
+        reject_cpi: false,
+        allowed_takers: [Pubkey::default(); MAX_ALLOWED_TAKERS],
+        sealed_bids: false,
+        payout_splits: [PayoutSplit::default(); MAX_PAYOUT_SPLITS],
+        donation_recipient: Pubkey::default(),
+        donation_bps: 0,
+        relist_receive: 0,
+        condition: TakeCondition::default(),
+        tranche: 0,
+        fill_interval: 0,
+        co_signer: Pubkey::default(),
+        require_compliance: false,
+        credential: CredentialGate::default(),
+        frontend: Pubkey::default(),
+        frontend_fee_bps: 0,
+    }
+}
+
+fn find_escrow(accounts: &[SnapshotAccount]) -> Option<(Pubkey, Escrow)> {
+    accounts.iter().find_map(|snapshot| {
+        let account = snapshot.get_account();
+        if *account.owner() != anchor_escrow::ID
+            || !account.data().starts_with(&Escrow::DISCRIMINATOR)
+        {
+            return None;
+        }
+        let escrow = Escrow::try_deserialize(&mut account.data()).ok()?;
+        Some((snapshot.pubkey(), escrow))
+    })
+}
+
+fn unpack_token(snapshot: &SnapshotAccount) -> Option<spl_token::state::Account> {
+    let account = snapshot.get_account();
+    if *account.owner() != spl_token::ID {
+        return None;
+    }
+    spl_token::state::Account::unpack(account.data()).ok()
+}
+
+fn token_accounts(
+    accounts: &[SnapshotAccount],
+) -> impl Iterator<Item = (Pubkey, spl_token::state::Account)> + '_ {
+    accounts
+        .iter()
+        .filter_map(|snapshot| Some((snapshot.pubkey(), unpack_token(snapshot)?)))
+}
+
+fn vault_amount(accounts: &[SnapshotAccount], escrow: &Pubkey) -> u64 {
+    token_accounts(accounts)
+        .find(|(_, account)| account.owner == *escrow)
+        .map_or(0, |(_, account)| account.amount)
+}
+
+/// Whether every owner's net balance change in each mint equals the sum of its expected
+/// `(owner, mint, amount)` flows. Flows are summed first, since the taker may also be the maker
+/// and mint_a may equal mint_b.
+fn flows_match(
+    pre_ix: &[SnapshotAccount],
+    post_ix: &[SnapshotAccount],
+    flows: &[(Pubkey, Pubkey, i128)],
+) -> bool {
+    flows.iter().all(|(owner, mint, _)| {
+        let expected = flows
+            .iter()
+            .filter(|(o, m, _)| o == owner && m == mint)
+            .map(|(_, _, amount)| amount)
+            .sum::<i128>();
+        balance_change(pre_ix, post_ix, owner, mint) == expected
+    })
+}
+
+/// Net change in the balance of `owner`'s `mint` token accounts across the instruction.
+fn balance_change(
+    pre_ix: &[SnapshotAccount],
+    post_ix: &[SnapshotAccount],
+    owner: &Pubkey,
+    mint: &Pubkey,
+) -> i128 {
+    let total = |accounts: &[SnapshotAccount]| {
+        let mut seen = Vec::new();
+        token_accounts(accounts)
+            .filter(|(key, account)| {
+                account.owner == *owner && account.mint == *mint && {
+                    let first = !seen.contains(key);
+                    seen.push(*key);
+                    first
+                }
+            })
+            .map(|(_, account)| account.amount as i128)
+            .sum::<i128>()
+    };
+    total(post_ix) - total(pre_ix)
+}
@@ -0,0 +1,43 @@
+use trident_client::fuzzing::*;
+mod fuzz_instructions;
+use fuzz_instructions::FuzzInstruction;
+use fuzz_instructions::Make;
+
+use anchor_escrow::entry as entry_anchor_escrow;
+use anchor_escrow::ID as PROGRAM_ID_ANCHOR_ESCROW;
+
+const PROGRAM_NAME_ANCHOR_ESCROW: &str = "anchor_escrow";
+
+struct InstructionsSequence;
+
+/// Every iteration opens an escrow and then runs a random sequence of makes, takes and refunds.
+/// Users, mints and seeds come from small pools, so most calls pair accounts that don't belong
+/// together and must be rejected.
+impl FuzzDataBuilder<FuzzInstruction> for InstructionsSequence {
+    pre_sequence!(Make);
+}
+
+/// `fn fuzz_iteration` runs during every fuzzing iteration.
+/// Modification is not required.
+fn fuzz_iteration<T: FuzzTestExecutor<U> + std::fmt::Display, U>(
+    fuzz_data: FuzzData<T, U>,
+    config: &Config,
+) {
+    let fuzzing_program_anchor_escrow = FuzzingProgram::new(
+        PROGRAM_NAME_ANCHOR_ESCROW,
+        &PROGRAM_ID_ANCHOR_ESCROW,
+        processor!(convert_entry!(entry_anchor_escrow)),
+    );
+
+    let mut client =
+        ProgramTestClientBlocking::new(&[fuzzing_program_anchor_escrow], config).unwrap();
+
+    let _ = fuzz_data.run_with_runtime(&mut client, config);
+}
+
+fn main() {
+    let config = Config::new();
+    fuzz_trident!(fuzz_ix: FuzzInstruction, |fuzz_data: InstructionsSequence| {
+        fuzz_iteration(fuzz_data, &config);
+    });
+}